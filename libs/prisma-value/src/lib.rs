@@ -232,6 +232,14 @@ where
     int.to_string().serialize(serializer)
 }
 
+/// `BigDecimal` carries arbitrary precision, but the JSON protocol this serializes into
+/// represents numbers as `f64` (the workspace doesn't enable serde_json's `arbitrary_precision`),
+/// so a decimal with more significant digits than an `f64` can hold loses some on the way out.
+/// Within that bound this is deterministic and lossless: workspace-wide `float_roundtrip` makes
+/// serde_json always emit the shortest decimal string that parses back to the exact same `f64`
+/// bits, so an ordinary-precision value like `19.99` round-trips unchanged and the same input
+/// always serializes to the same bytes, with no scientific notation in the range such values fall
+/// in.
 fn serialize_decimal<S>(decimal: &BigDecimal, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
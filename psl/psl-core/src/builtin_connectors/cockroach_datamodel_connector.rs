@@ -44,6 +44,7 @@ const CAPABILITIES: ConnectorCapabilities = enumflags2::make_bitflags!(Connector
     JsonFiltering |
     JsonFilteringArrayPath |
     JsonArrayContains |
+    JsonOrdering |
     NamedPrimaryKeys |
     NamedForeignKeys |
     RelationFieldsInArbitraryOrder |
@@ -39,6 +39,7 @@ pub const CAPABILITIES: ConnectorCapabilities = enumflags2::make_bitflags!(Conne
     JsonFilteringJsonPath |
     JsonFilteringAlphanumeric |
     JsonArrayContains |
+    JsonOrdering |
     CreateManyWriteableAutoIncId |
     AutoIncrement |
     CompoundIds |
@@ -48,6 +48,7 @@ pub const CAPABILITIES: ConnectorCapabilities = enumflags2::make_bitflags!(Conne
     JsonFilteringAlphanumeric |
     JsonFilteringAlphanumericFieldRef |
     JsonArrayContains |
+    JsonOrdering |
     MultiSchema |
     NamedForeignKeys |
     NamedPrimaryKeys |
@@ -33,6 +33,7 @@ pub const CAPABILITIES: ConnectorCapabilities = enumflags2::make_bitflags!(Conne
     Json |
     JsonFiltering |
     JsonFilteringJsonPath |
+    JsonOrdering |
     AdvancedJsonNullability |
     Enums
 });
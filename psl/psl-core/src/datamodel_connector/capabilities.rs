@@ -84,6 +84,7 @@ capabilities!(
     JsonFilteringAlphanumeric, // Connector supports alphanumeric json filters (gt, gte, lt, lte...).
     JsonFilteringAlphanumericFieldRef, // Connector supports alphanumeric json filters against a json field reference.
     JsonArrayContains, // Connector supports the contains operator for json fields.
+    JsonOrdering, // Connector supports ordering by a value extracted from a json field via a path.
     CompoundIds,
     AnyId, // Any (or combination of) uniques and not only id fields can constitute an id for a model.
     NativeFullTextSearch,
@@ -431,7 +431,7 @@ pub trait Comparable<'a> {
     /// let query = Select::from_table("users").so_that("foo".like("%bar%"));
     /// let (sql, params) = Sqlite::build(query)?;
     ///
-    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` LIKE ?", sql);
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` LIKE ? ESCAPE '\\'", sql);
     ///
     /// assert_eq!(
     ///     vec![
@@ -454,7 +454,7 @@ pub trait Comparable<'a> {
     /// let query = Select::from_table("users").so_that("foo".not_like("%bar%"));
     /// let (sql, params) = Sqlite::build(query)?;
     ///
-    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` NOT LIKE ?", sql);
+    /// assert_eq!("SELECT `users`.* FROM `users` WHERE `foo` NOT LIKE ? ESCAPE '\\'", sql);
     ///
     /// assert_eq!(
     ///     vec![
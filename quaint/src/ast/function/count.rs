@@ -1,10 +1,49 @@
 use super::Function;
-use crate::ast::Expression;
+use crate::ast::{Column, Expression, IntoOrderDefinition, Over};
 
 #[derive(Debug, Clone, PartialEq)]
 /// Returns the number of rows that matches a specified criteria.
 pub struct Count<'a> {
     pub(crate) exprs: Vec<Expression<'a>>,
+    /// `Some` turns the count into a window function (`COUNT(*) OVER(...)`) instead of
+    /// collapsing the query into a single row; `None` (the default) renders a plain `COUNT(...)`.
+    pub(crate) over: Option<Over<'a>>,
+}
+
+impl<'a> Count<'a> {
+    /// Turns this into a window function counting over the given partitioning/ordering instead
+    /// of collapsing the whole query into a single row, e.g. `COUNT(*) OVER(PARTITION BY ...)` to
+    /// report a total alongside each row of a paginated result set. An empty `Over` still renders
+    /// `COUNT(*) OVER()`, counting across the whole result set.
+    pub fn over<T>(mut self, over: T) -> Self
+    where
+        T: Into<Over<'a>>,
+    {
+        self.over = Some(over.into());
+        self
+    }
+
+    /// Define the ordering of the window the count is taken over. Implies [`Self::over`].
+    pub fn order_by<T>(mut self, value: T) -> Self
+    where
+        T: IntoOrderDefinition<'a>,
+    {
+        let mut over = self.over.unwrap_or_default();
+        over.ordering = over.ordering.append(value.into_order_definition());
+        self.over = Some(over);
+        self
+    }
+
+    /// Define the partitioning of the window the count is taken over. Implies [`Self::over`].
+    pub fn partition_by<T>(mut self, partition: T) -> Self
+    where
+        T: Into<Column<'a>>,
+    {
+        let mut over = self.over.unwrap_or_default();
+        over.partitioning.push(partition.into());
+        self.over = Some(over);
+        self
+    }
 }
 
 /// Count of the underlying table where the given expression is not null.
@@ -24,7 +63,22 @@ where
 {
     let fun = Count {
         exprs: vec![expr.into()],
+        over: None,
     };
 
     fun.into()
 }
+
+/// Like [`count`], but returns the unwrapped builder instead of a [`Function`], so `.over(...)`/
+/// `.partition_by(...)`/`.order_by(...)` can be chained onto it first (the same shape as
+/// [`row_number`](super::row_number)), e.g. `windowed_count(asterisk()).over(Over::default())`
+/// for a `COUNT(*) OVER()` reporting a total alongside each row of a paginated result set.
+pub fn windowed_count<'a, T>(expr: T) -> Count<'a>
+where
+    T: Into<Expression<'a>>,
+{
+    Count {
+        exprs: vec![expr.into()],
+        over: None,
+    }
+}
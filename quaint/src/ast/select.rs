@@ -16,6 +16,7 @@ pub struct Select<'a> {
     pub(crate) joins: Vec<Join<'a>>,
     pub(crate) ctes: Vec<CommonTableExpression<'a>>,
     pub(crate) comment: Option<Cow<'a, str>>,
+    pub(crate) lock: Option<Lock>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -24,6 +25,15 @@ pub enum DistinctType<'a> {
     OnClause(Vec<Expression<'a>>),
 }
 
+/// A row-level lock requested on the rows a `SELECT` returns.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Lock {
+    /// `FOR UPDATE`: blocks other transactions from locking, updating or deleting these rows until the current transaction ends.
+    Update,
+    /// `FOR SHARE`: blocks other transactions from updating or deleting these rows, but allows them to take their own share lock.
+    Share,
+}
+
 impl<'a> From<Select<'a>> for Expression<'a> {
     fn from(sel: Select<'a>) -> Expression<'a> {
         Expression {
@@ -277,6 +287,15 @@ impl<'a> Select<'a> {
         self
     }
 
+    /// Whether this `SELECT`'s `WHERE` clause is exactly the constant-false predicate
+    /// (`ConditionTree::NegativeCondition`, rendered as `1=0`), i.e. the query is known at
+    /// construction time to never match a row. Lets a caller that built the condition tree
+    /// itself (and so already knows this) carry that fact forward structurally, instead of a
+    /// later consumer having to re-derive it by pattern-matching the rendered SQL text.
+    pub fn is_known_empty(&self) -> bool {
+        matches!(self.conditions, Some(ConditionTree::NegativeCondition))
+    }
+
     /// Adds an additional `WHERE` condition to the query combining the possible
     /// previous condition with `AND`. See
     /// [Comparable](trait.Comparable.html#required-methods) for more examples.
@@ -603,6 +622,23 @@ impl<'a> Select<'a> {
         self
     }
 
+    /// Locks the selected rows for the duration of the current transaction.
+    ///
+    /// ```rust
+    /// # use quaint::{ast::*, visitor::{Visitor, Postgres}};
+    /// # fn main() -> Result<(), quaint::error::Error> {
+    /// let query = Select::from_table("users").lock(Lock::Update);
+    /// let (sql, _) = Postgres::build(query)?;
+    ///
+    /// assert_eq!("SELECT \"users\".* FROM \"users\" FOR UPDATE", sql);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lock(mut self, lock: Lock) -> Self {
+        self.lock = Some(lock);
+        self
+    }
+
     /// Adds a common table expression to the select.
     ///
     /// ```rust
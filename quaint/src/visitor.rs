@@ -356,6 +356,14 @@ pub trait Visitor<'a> {
             }
 
             self.visit_limit_and_offset(select.limit, select.offset)?;
+
+            if let Some(lock) = select.lock {
+                self.write(" ")?;
+                self.write(match lock {
+                    Lock::Update => "FOR UPDATE",
+                    Lock::Share => "FOR SHARE",
+                })?;
+            }
         } else if select.columns.is_empty() {
             self.write(" *")?;
         } else {
@@ -782,6 +790,10 @@ pub trait Visitor<'a> {
         self.visit_expression(left)?;
         self.write(" LIKE ")?;
         self.visit_expression(right)?;
+        // Backslash is only the implicit LIKE escape character on Postgres/MySQL; SQLite and
+        // MSSQL treat it as a literal unless told otherwise, so spell it out explicitly here to
+        // match what `escape_like` in the query builder assumes everywhere.
+        self.write(" ESCAPE '\\'")?;
 
         Ok(())
     }
@@ -790,6 +802,7 @@ pub trait Visitor<'a> {
         self.visit_expression(left)?;
         self.write(" NOT LIKE ")?;
         self.visit_expression(right)?;
+        self.write(" ESCAPE '\\'")?;
 
         Ok(())
     }
@@ -1073,6 +1086,15 @@ pub trait Visitor<'a> {
                     self.write("COUNT")?;
                     self.surround_with("(", ")", |ref mut s| s.visit_columns(fun_count.exprs))?;
                 }
+
+                if let Some(over) = fun_count.over {
+                    if over.is_empty() {
+                        self.write(" OVER()")?;
+                    } else {
+                        self.write(" OVER")?;
+                        self.surround_with("(", ")", |ref mut s| s.visit_partitioning(over))?;
+                    }
+                }
             }
             FunctionType::AggregateToString(agg) => {
                 self.visit_aggregate_to_string(agg.value.as_ref().clone())?;
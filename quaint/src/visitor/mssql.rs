@@ -907,7 +907,7 @@ mod tests {
 
     #[test]
     fn test_select_where_like() {
-        let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1", vec!["%meow%"]);
+        let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1 ESCAPE '\\'", vec!["%meow%"]);
 
         let query = Select::from_table("naukio").so_that("word".like("%meow%"));
         let (sql, params) = Mssql::build(query).unwrap();
@@ -919,7 +919,7 @@ mod tests {
     #[test]
     fn test_select_where_not_like() {
         let expected = expected_values(
-            "SELECT [naukio].* FROM [naukio] WHERE [word] NOT LIKE @P1",
+            "SELECT [naukio].* FROM [naukio] WHERE [word] NOT LIKE @P1 ESCAPE '\\'",
             vec!["%meow%"],
         );
 
@@ -932,7 +932,7 @@ mod tests {
 
     #[test]
     fn test_select_where_begins_with() {
-        let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1", vec!["%meow"]);
+        let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1 ESCAPE '\\'", vec!["%meow"]);
 
         let query = Select::from_table("naukio").so_that("word".like("%meow"));
         let (sql, params) = Mssql::build(query).unwrap();
@@ -944,7 +944,7 @@ mod tests {
     #[test]
     fn test_select_where_not_begins_with() {
         let expected = expected_values(
-            "SELECT [naukio].* FROM [naukio] WHERE [word] NOT LIKE @P1",
+            "SELECT [naukio].* FROM [naukio] WHERE [word] NOT LIKE @P1 ESCAPE '\\'",
             vec!["%meow"],
         );
 
@@ -957,7 +957,7 @@ mod tests {
 
     #[test]
     fn test_select_where_ends_into() {
-        let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1", vec!["meow%"]);
+        let expected = expected_values("SELECT [naukio].* FROM [naukio] WHERE [word] LIKE @P1 ESCAPE '\\'", vec!["meow%"]);
 
         let query = Select::from_table("naukio").so_that("word".like("meow%"));
         let (sql, params) = Mssql::build(query).unwrap();
@@ -969,7 +969,7 @@ mod tests {
     #[test]
     fn test_select_where_not_ends_into() {
         let expected = expected_values(
-            "SELECT [naukio].* FROM [naukio] WHERE [word] NOT LIKE @P1",
+            "SELECT [naukio].* FROM [naukio] WHERE [word] NOT LIKE @P1 ESCAPE '\\'",
             vec!["meow%"],
         );
 
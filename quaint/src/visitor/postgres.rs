@@ -649,6 +649,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
 
         self.write(" LIKE ")?;
         self.visit_expression(right)?;
+        self.write(" ESCAPE '\\'")?;
 
         Ok(())
     }
@@ -665,6 +666,7 @@ impl<'a> Visitor<'a> for Postgres<'a> {
 
         self.write(" NOT LIKE ")?;
         self.visit_expression(right)?;
+        self.write(" ESCAPE '\\'")?;
 
         Ok(())
     }
@@ -1196,7 +1198,7 @@ mod tests {
     #[test]
     fn test_like_cast_to_string() {
         let expected = expected_values(
-            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text LIKE $1"#,
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text LIKE $1 ESCAPE '\'"#,
             vec!["%foo%"],
         );
 
@@ -1210,7 +1212,7 @@ mod tests {
     #[test]
     fn test_not_like_cast_to_string() {
         let expected = expected_values(
-            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text NOT LIKE $1"#,
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text NOT LIKE $1 ESCAPE '\'"#,
             vec!["%foo%"],
         );
 
@@ -1224,7 +1226,7 @@ mod tests {
     #[test]
     fn test_begins_with_cast_to_string() {
         let expected = expected_values(
-            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text LIKE $1"#,
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text LIKE $1 ESCAPE '\'"#,
             vec!["%foo"],
         );
 
@@ -1238,7 +1240,7 @@ mod tests {
     #[test]
     fn test_not_begins_with_cast_to_string() {
         let expected = expected_values(
-            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text NOT LIKE $1"#,
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text NOT LIKE $1 ESCAPE '\'"#,
             vec!["%foo"],
         );
 
@@ -1252,7 +1254,7 @@ mod tests {
     #[test]
     fn test_ends_with_cast_to_string() {
         let expected = expected_values(
-            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text LIKE $1"#,
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text LIKE $1 ESCAPE '\'"#,
             vec!["foo%"],
         );
 
@@ -1266,7 +1268,7 @@ mod tests {
     #[test]
     fn test_not_ends_with_cast_to_string() {
         let expected = expected_values(
-            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text NOT LIKE $1"#,
+            r#"SELECT "test".* FROM "test" WHERE "jsonField"::text NOT LIKE $1 ESCAPE '\'"#,
             vec!["foo%"],
         );
 
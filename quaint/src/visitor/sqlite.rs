@@ -696,7 +696,7 @@ mod tests {
 
     #[test]
     fn test_select_where_like() {
-        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ?", vec!["%meow%"]);
+        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ? ESCAPE '\\'", vec!["%meow%"]);
 
         let query = Select::from_table("naukio").so_that("word".like("%meow%"));
         let (sql, params) = Sqlite::build(query).unwrap();
@@ -708,7 +708,7 @@ mod tests {
     #[test]
     fn test_select_where_not_like() {
         let expected = expected_values(
-            "SELECT `naukio`.* FROM `naukio` WHERE `word` NOT LIKE ?",
+            "SELECT `naukio`.* FROM `naukio` WHERE `word` NOT LIKE ? ESCAPE '\\'",
             vec!["%meow%"],
         );
 
@@ -721,7 +721,7 @@ mod tests {
 
     #[test]
     fn test_select_where_begins_with() {
-        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ?", vec!["%meow"]);
+        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ? ESCAPE '\\'", vec!["%meow"]);
 
         let query = Select::from_table("naukio").so_that("word".like("%meow"));
         let (sql, params) = Sqlite::build(query).unwrap();
@@ -732,7 +732,7 @@ mod tests {
 
     #[test]
     fn test_select_where_not_begins_with() {
-        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` NOT LIKE ?", vec!["%meow"]);
+        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` NOT LIKE ? ESCAPE '\\'", vec!["%meow"]);
 
         let query = Select::from_table("naukio").so_that("word".not_like("%meow"));
         let (sql, params) = Sqlite::build(query).unwrap();
@@ -743,7 +743,7 @@ mod tests {
 
     #[test]
     fn test_select_where_ends_into() {
-        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ?", vec!["meow%"]);
+        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` LIKE ? ESCAPE '\\'", vec!["meow%"]);
 
         let query = Select::from_table("naukio").so_that("word".like("meow%"));
         let (sql, params) = Sqlite::build(query).unwrap();
@@ -754,7 +754,7 @@ mod tests {
 
     #[test]
     fn test_select_where_not_ends_into() {
-        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` NOT LIKE ?", vec!["meow%"]);
+        let expected = expected_values("SELECT `naukio`.* FROM `naukio` WHERE `word` NOT LIKE ? ESCAPE '\\'", vec!["meow%"]);
 
         let query = Select::from_table("naukio").so_that("word".not_like("meow%"));
         let (sql, params) = Sqlite::build(query).unwrap();
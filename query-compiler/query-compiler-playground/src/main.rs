@@ -81,7 +81,7 @@ pub fn main() -> anyhow::Result<()> {
     let ctx = Context::new(&connection_info, None);
     let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
 
-    let expr = query_compiler::translate(graph, &builder)?;
+    let expr = query_compiler::translate(graph, &builder, &Default::default())?;
 
     println!("{}", expr.pretty_print(true, 80)?);
 
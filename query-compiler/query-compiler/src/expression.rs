@@ -1,7 +1,10 @@
 use query_builder::DbQuery;
+use query_structure::{PlaceholderType, PrismaValue};
 use serde::Serialize;
 
 mod format;
+pub(crate) mod lint;
+pub(crate) mod transform;
 
 #[derive(Debug, Serialize)]
 pub struct Binding {
@@ -33,7 +36,15 @@ pub struct JoinExpression {
 #[serde(tag = "type", content = "args", rename_all = "camelCase")]
 pub enum Expression {
     /// Sequence of statements. The whole sequence evaluates to the result of the last expression.
-    Seq(Vec<Expression>),
+    Seq {
+        statements: Vec<Expression>,
+        /// Hints that every statement is independent of its siblings (no data or execution-order
+        /// dependency between them) and the builder that produced them supports sending several
+        /// statements in one round trip, so an interpreter may pipeline them instead of waiting
+        /// for each response before issuing the next. Purely a latency hint: an interpreter that
+        /// ignores it still gets the same result, just over more round trips.
+        pipelined: bool,
+    },
 
     /// Get binding value.
     Get { name: String },
@@ -65,8 +76,9 @@ pub enum Expression {
     /// Asserts that the result of the expression is at most one record.
     Unique(Box<Expression>),
 
-    /// Asserts that the result of the expression is at least one record.
-    Required(Box<Expression>),
+    /// Asserts that the result of the expression is at least one record, failing with `message`
+    /// otherwise. Used to compile a `*OrThrow` operation's not-found check.
+    Required { expr: Box<Expression>, message: String },
 
     /// Application-level join.
     Join {
@@ -77,6 +89,17 @@ pub enum Expression {
     /// Get a field from a record or records. If the argument is a list of records,
     /// returns a list of values of this field.
     MapField { field: String, records: Box<Expression> },
+
+    /// A literal value bound in the enclosing scope, so that it can be shared
+    /// between sibling queries via a placeholder instead of being recomputed
+    /// or re-sent for each of them.
+    Value(PrismaValue),
+
+    /// Hints that the result of the wrapped expression should be grouped by
+    /// column rather than returned record-by-record, e.g. to hand it to an
+    /// Arrow-backed consumer. Purely a shape hint: it doesn't change which
+    /// queries run or what they return.
+    Columnar(Box<Expression>),
 }
 
 #[derive(Debug, Clone)]
@@ -117,9 +140,68 @@ impl Expression {
         Ok(String::from_utf8(buf.into_inner())?)
     }
 
+    /// A rough, relative estimate of how expensive this plan is to execute:
+    /// the number of queries it issues, weighted up for queries nested under
+    /// an in-memory join, since those fan out once per parent record rather
+    /// than running once for the whole plan. `Let` bindings are counted once,
+    /// unweighted.
+    pub fn estimated_cost(&self) -> u64 {
+        match self {
+            Expression::Seq { statements, .. } => statements.iter().map(Expression::estimated_cost).sum(),
+            Expression::Sum(vec) | Expression::Concat(vec) => vec.iter().map(Expression::estimated_cost).sum(),
+            Expression::Get { .. } | Expression::GetFirstNonEmpty { .. } | Expression::Value(_) => 0,
+            Expression::Let { bindings, expr } => {
+                bindings.iter().map(|b| b.expr.estimated_cost()).sum::<u64>() + expr.estimated_cost()
+            }
+            Expression::Query(_) | Expression::Execute(_) => 1,
+            Expression::Reverse(expression) | Expression::Unique(expression) => expression.estimated_cost(),
+            Expression::Required { expr, .. } => expr.estimated_cost(),
+            Expression::Join { parent, children } => {
+                parent.estimated_cost()
+                    + children
+                        .iter()
+                        .map(|join| join.child.estimated_cost() * 10)
+                        .sum::<u64>()
+            }
+            Expression::MapField { records, .. } => records.estimated_cost(),
+            Expression::Columnar(expression) => expression.estimated_cost(),
+        }
+    }
+
+    /// Whether running this plan needs to be wrapped in a transaction to be
+    /// safe, i.e. whether it issues more than one database statement. A
+    /// single `findUnique` or a single-statement write can run standalone;
+    /// a nested create or anything else that issues dependent statements
+    /// needs the atomicity (and, for reads-after-writes, consistency) a
+    /// transaction provides.
+    pub fn requires_transaction(&self) -> bool {
+        self.statement_count() > 1
+    }
+
+    fn statement_count(&self) -> u64 {
+        match self {
+            Expression::Seq { statements, .. } => statements.iter().map(Expression::statement_count).sum(),
+            Expression::Sum(vec) | Expression::Concat(vec) => vec.iter().map(Expression::statement_count).sum(),
+            Expression::Get { .. } | Expression::GetFirstNonEmpty { .. } | Expression::Value(_) => 0,
+            Expression::Let { bindings, expr } => {
+                bindings.iter().map(|b| b.expr.statement_count()).sum::<u64>() + expr.statement_count()
+            }
+            Expression::Query(_) | Expression::Execute(_) => 1,
+            Expression::Reverse(expression) | Expression::Unique(expression) => expression.statement_count(),
+            Expression::Required { expr, .. } => expr.statement_count(),
+            Expression::Join { parent, children } => {
+                parent.statement_count() + children.iter().map(|join| join.child.statement_count()).sum::<u64>()
+            }
+            Expression::MapField { records, .. } => records.statement_count(),
+            Expression::Columnar(expression) => expression.statement_count(),
+        }
+    }
+
     pub fn r#type(&self) -> ExpressionType {
         match self {
-            Expression::Seq(vec) => vec.iter().last().map_or(ExpressionType::Scalar, Expression::r#type),
+            Expression::Seq { statements, .. } => {
+                statements.iter().last().map_or(ExpressionType::Scalar, Expression::r#type)
+            }
             Expression::Get { .. } => ExpressionType::Dynamic,
             Expression::Let { expr, .. } => expr.r#type(),
             Expression::GetFirstNonEmpty { .. } => ExpressionType::Dynamic,
@@ -134,10 +216,77 @@ impl Expression {
                 ExpressionType::List(inner) => inner.as_ref().clone(),
                 _ => expression.r#type(),
             },
-            Expression::Required(expression) => expression.r#type(),
+            Expression::Required { expr, .. } => expr.r#type(),
             Expression::Join { parent, .. } => parent.r#type(),
             Expression::MapField { records, .. } => records.r#type(),
+            Expression::Value(_) => ExpressionType::Scalar,
+            Expression::Columnar(expression) => expression.r#type(),
+        }
+    }
+}
+
+/// A single named placeholder referenced somewhere in a plan, i.e. a value
+/// the plan expects to be resolved from an outer `Let` binding rather than
+/// being a literal constant baked into a query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BoundParameter {
+    pub name: String,
+    pub r#type: PlaceholderType,
+}
+
+impl Expression {
+    /// Walks the whole plan and returns every distinct named placeholder it
+    /// references, in first-occurrence order. Useful for client tooling that
+    /// needs to know, up front, which bindings a plan expects without
+    /// interpreting it.
+    pub fn bind_parameters(&self) -> Vec<BoundParameter> {
+        let mut params = Vec::new();
+        self.collect_bind_parameters(&mut params);
+        params
+    }
+
+    fn collect_bind_parameters(&self, params: &mut Vec<BoundParameter>) {
+        match self {
+            Expression::Query(q) | Expression::Execute(q) => collect_from_db_query(q, params),
+            Expression::Seq { statements, .. } => statements.iter().for_each(|e| e.collect_bind_parameters(params)),
+            Expression::Sum(vec) | Expression::Concat(vec) => {
+                vec.iter().for_each(|e| e.collect_bind_parameters(params))
+            }
+            Expression::Let { bindings, expr } => {
+                bindings.iter().for_each(|b| b.expr.collect_bind_parameters(params));
+                expr.collect_bind_parameters(params);
+            }
+            Expression::Reverse(e) | Expression::Unique(e) => e.collect_bind_parameters(params),
+            Expression::Required { expr, .. } => expr.collect_bind_parameters(params),
+            Expression::Join { parent, children } => {
+                parent.collect_bind_parameters(params);
+                children.iter().for_each(|join| join.child.collect_bind_parameters(params));
+            }
+            Expression::MapField { records, .. } => records.collect_bind_parameters(params),
+            Expression::Columnar(expression) => expression.collect_bind_parameters(params),
+            Expression::Get { .. } | Expression::GetFirstNonEmpty { .. } | Expression::Value(_) => {}
+        }
+    }
+}
+
+fn collect_from_db_query(query: &DbQuery, params: &mut Vec<BoundParameter>) {
+    for value in &query.params {
+        collect_from_value(value, params);
+    }
+}
+
+fn collect_from_value(value: &PrismaValue, params: &mut Vec<BoundParameter>) {
+    match value {
+        PrismaValue::Placeholder { name, r#type } => {
+            if !params.iter().any(|p| p.name == *name) {
+                params.push(BoundParameter {
+                    name: name.clone(),
+                    r#type: r#type.clone(),
+                });
+            }
         }
+        PrismaValue::List(values) => values.iter().for_each(|v| collect_from_value(v, params)),
+        _ => {}
     }
 }
 
@@ -41,7 +41,7 @@ where
 
     pub fn expression(&'a self, expression: &'a Expression) -> DocBuilder<'a, PrettyPrinter<'a, D>, ColorSpec> {
         match expression {
-            Expression::Seq(vec) => self.seq(vec),
+            Expression::Seq { statements, pipelined } => self.seq(statements, *pipelined),
             Expression::Get { name } => self.get(name),
             Expression::Let { bindings, expr } => self.r#let(bindings, expr),
             Expression::GetFirstNonEmpty { names } => self.get_first_non_empty(names),
@@ -51,9 +51,11 @@ where
             Expression::Sum(vec) => self.function("sum", vec),
             Expression::Concat(vec) => self.function("concat", vec),
             Expression::Unique(expression) => self.unary_function("unique", expression),
-            Expression::Required(expression) => self.unary_function("required", expression),
+            Expression::Required { expr, message } => self.required(expr, message),
             Expression::Join { parent, children } => self.join(parent, children),
             Expression::MapField { field, records } => self.map_field(field, records),
+            Expression::Value(value) => self.value(value),
+            Expression::Columnar(expression) => self.unary_function("columnar", expression),
         }
     }
 
@@ -145,11 +147,25 @@ where
             .append(self.expression(arg).parens())
     }
 
-    fn seq(&'a self, vec: &'a [Expression]) -> DocBuilder<'a, PrettyPrinter<'a, D>, ColorSpec> {
-        self.intersperse(
-            vec.iter().map(|expr| self.expression(expr)),
-            self.text(";").append(self.line()),
-        )
+    fn required(&'a self, arg: &'a Expression, message: &'a str) -> DocBuilder<'a, PrettyPrinter<'a, D>, ColorSpec> {
+        self.text("required")
+            .annotate(color_fn())
+            .append(self.space())
+            .append(
+                self.expression(arg)
+                    .append(self.text(",").append(self.space()))
+                    .append(self.text(format!("{message:?}")).annotate(color_lit()))
+                    .parens(),
+            )
+    }
+
+    fn seq(&'a self, vec: &'a [Expression], pipelined: bool) -> DocBuilder<'a, PrettyPrinter<'a, D>, ColorSpec> {
+        let separator = if pipelined {
+            self.text(";").append(self.space()).append(self.keyword("pipelined"))
+        } else {
+            self.text(";")
+        };
+        self.intersperse(vec.iter().map(|expr| self.expression(expr)), separator.append(self.line()))
     }
 
     fn get(&'a self, name: &'a str) -> DocBuilder<'a, PrettyPrinter<'a, D>, ColorSpec> {
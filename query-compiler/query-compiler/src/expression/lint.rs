@@ -0,0 +1,49 @@
+use super::Expression;
+
+/// Flags writes that return row data (e.g. `RETURNING id`) but whose result
+/// ends up neither bound to a later step nor returned from the plan. This
+/// doesn't change the compiled plan — it's a diagnostic for `QueryGraph` bugs,
+/// since a write that's meant to be fire-and-forget shouldn't have asked for a
+/// result in the first place.
+pub(crate) fn warn_on_dangling_writes(expr: &Expression) {
+    match expr {
+        Expression::Seq { statements, .. } => {
+            if let Some((last, init)) = statements.split_last() {
+                for e in init {
+                    warn_if_dropped(e);
+                    warn_on_dangling_writes(e);
+                }
+                warn_on_dangling_writes(last);
+            }
+        }
+        Expression::Let { bindings, expr } => {
+            bindings.iter().for_each(|b| warn_on_dangling_writes(&b.expr));
+            warn_on_dangling_writes(expr);
+        }
+        Expression::Sum(exprs) | Expression::Concat(exprs) => exprs.iter().for_each(warn_on_dangling_writes),
+        Expression::Reverse(e) | Expression::Unique(e) | Expression::Columnar(e) => warn_on_dangling_writes(e),
+        Expression::Required { expr, .. } => warn_on_dangling_writes(expr),
+        Expression::Join { parent, children } => {
+            warn_on_dangling_writes(parent);
+            children.iter().for_each(|join| warn_on_dangling_writes(&join.child));
+        }
+        Expression::MapField { records, .. } => warn_on_dangling_writes(records),
+        Expression::Get { .. }
+        | Expression::GetFirstNonEmpty { .. }
+        | Expression::Value(_)
+        | Expression::Query(_)
+        | Expression::Execute(_) => {}
+    }
+}
+
+fn warn_if_dropped(expr: &Expression) {
+    if returns_row_data(expr) {
+        tracing::warn!("a write's result is computed but never bound to a later step or returned from the plan");
+    }
+}
+
+/// Whether evaluating `expr` produces row data (as opposed to just a rows-affected count),
+/// i.e. whether it came from a write that asked for `RETURNING`.
+fn returns_row_data(expr: &Expression) -> bool {
+    matches!(expr, Expression::Query(_) | Expression::Unique(_) | Expression::Required { .. })
+}
@@ -0,0 +1,635 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use query_builder::{DbQuery, QueryBuilder};
+use query_structure::{PlaceholderType, PrismaValue};
+
+use super::{Binding, Expression, JoinExpression};
+
+/// Runs every translate-time `Expression` rewrite. Passes only ever narrow or
+/// preserve the meaning of the tree they're given; they never need to see the
+/// `QueryGraph` the `Expression` was translated from.
+pub(crate) fn optimize(expr: Expression) -> Expression {
+    let expr = coalesce_shared_timestamps(expr);
+    let expr = flatten_nested_get_first_non_empty(expr);
+    short_circuit_empty_branches(expr)
+}
+
+// No "merge a findFirst into an existence check" pass lives here: `Expression` has no
+// conditional-branch variant to detect a read feeding one (`Node::Flow`, the query graph's
+// if/else, is unimplemented!() in `translate.rs` — this compiler never lowers a branch to
+// `Expression` in the first place), and the one case the request calls out by name, upsert,
+// already compiles to a single native `INSERT ... ON CONFLICT` statement in
+// `sql_query_builder::write::native_upsert` with no separate find to merge away. Revisit this
+// once/if `Expression` grows a branch construct that could actually carry an existence-only read.
+
+/// Replaces a [`Expression::Query`] that the query builder already proved can never return a
+/// row — it compiled the whole `WHERE` clause down to the constant-false predicate
+/// ([`ConditionTree::NegativeCondition`], e.g. from an empty `id: { in: [] }`), and flagged the
+/// resulting [`DbQuery::known_empty`] — with an empty [`Expression::Concat`], so the plan no
+/// longer issues a statement whose result is already known. A [`Expression::Join`] whose `parent`
+/// folds to that empty constant is replaced by the constant too, dropping its `children` (an
+/// in-memory join onto zero parent rows can't produce output, however many children there are to
+/// read), pruning the useless nested reads instead of just the top-level one.
+///
+/// This only catches the single vacuous predicate the query builder already collapses to a
+/// constant-false condition on its own; proving a branch empty from a more general contradiction
+/// (e.g. across an `AND`/`OR` it doesn't simplify, or a filter expressed over several statements)
+/// is out of scope here.
+///
+/// [`ConditionTree::NegativeCondition`]: quaint::ast::ConditionTree::NegativeCondition
+/// [`DbQuery::known_empty`]: DbQuery::known_empty
+fn short_circuit_empty_branches(expr: Expression) -> Expression {
+    rewrite_empty_branches(expr, &HashSet::new())
+}
+
+/// `known_empty` names the `Let` bindings already proven, earlier in this same walk, to hold the
+/// empty constant — so a later `Get` of one of them (the shape `add_inmemory_join` produces: the
+/// parent read bound to `@parent`, then referenced from inside the `Join`) is recognized as empty
+/// too, without re-deriving it from the binding's `Expression` each time it's read.
+fn rewrite_empty_branches(expr: Expression, known_empty: &HashSet<String>) -> Expression {
+    match expr {
+        Expression::Query(q) if is_vacuously_empty(&q) => Expression::Concat(Vec::new()),
+        Expression::Get { name } if known_empty.contains(&name) => Expression::Concat(Vec::new()),
+        Expression::Seq { statements, pipelined } => Expression::Seq {
+            statements: statements.into_iter().map(|e| rewrite_empty_branches(e, known_empty)).collect(),
+            pipelined,
+        },
+        Expression::Sum(exprs) => {
+            Expression::Sum(exprs.into_iter().map(|e| rewrite_empty_branches(e, known_empty)).collect())
+        }
+        Expression::Concat(exprs) => {
+            Expression::Concat(exprs.into_iter().map(|e| rewrite_empty_branches(e, known_empty)).collect())
+        }
+        Expression::Let { bindings, expr } => {
+            let mut known_empty = known_empty.clone();
+            let bindings = bindings
+                .into_iter()
+                .map(|b| {
+                    let expr = rewrite_empty_branches(b.expr, &known_empty);
+                    if matches!(&expr, Expression::Concat(exprs) if exprs.is_empty()) {
+                        known_empty.insert(b.name.clone());
+                    }
+                    Binding::new(b.name, expr)
+                })
+                .collect();
+            Expression::Let {
+                bindings,
+                expr: Box::new(rewrite_empty_branches(*expr, &known_empty)),
+            }
+        }
+        Expression::Reverse(e) => Expression::Reverse(Box::new(rewrite_empty_branches(*e, known_empty))),
+        Expression::Unique(e) => Expression::Unique(Box::new(rewrite_empty_branches(*e, known_empty))),
+        Expression::Required { expr, message } => Expression::Required {
+            expr: Box::new(rewrite_empty_branches(*expr, known_empty)),
+            message,
+        },
+        Expression::Columnar(e) => Expression::Columnar(Box::new(rewrite_empty_branches(*e, known_empty))),
+        Expression::Join { parent, children } => {
+            let parent = rewrite_empty_branches(*parent, known_empty);
+            if matches!(&parent, Expression::Concat(exprs) if exprs.is_empty()) {
+                parent
+            } else {
+                Expression::Join {
+                    parent: Box::new(parent),
+                    children: children
+                        .into_iter()
+                        .map(|join| JoinExpression {
+                            child: rewrite_empty_branches(join.child, known_empty),
+                            ..join
+                        })
+                        .collect(),
+                }
+            }
+        }
+        Expression::MapField { field, records } => Expression::MapField {
+            field,
+            records: Box::new(rewrite_empty_branches(*records, known_empty)),
+        },
+        other @ (Expression::GetFirstNonEmpty { .. } | Expression::Value(_) | Expression::Execute(_)) => other,
+        other @ (Expression::Get { .. } | Expression::Query(_)) => other,
+    }
+}
+
+/// Whether `query` is known at compile time to return no rows, i.e. the query builder already
+/// flagged it as [`known_empty`] while constructing its filter. Read off that flag directly
+/// instead of re-deriving it from the rendered SQL text: a query's `WHERE` clause can itself
+/// contain a nested `WHERE` (e.g. an `EXISTS`-based relation filter, or a derived-table subquery),
+/// so sniffing the rendered string for a top-level `WHERE ... 1=0` is fragile against anything
+/// that changes where the real top-level predicate ends up in the text.
+///
+/// [`known_empty`]: DbQuery::known_empty
+fn is_vacuously_empty(query: &DbQuery) -> bool {
+    query.known_empty
+}
+
+/// Batch writes (`createMany`, filter-based `updateMany`) translate to a
+/// `Concat`/`Sum` of otherwise-independent queries. When every row in the
+/// batch binds the same client-generated timestamp (e.g. a shared
+/// `updatedAt`), this rewrite hoists that one value into a `Let` binding and
+/// has every query reference it through a placeholder, so the batch carries a
+/// single, guaranteed-identical timestamp instead of repeating it per row.
+fn coalesce_shared_timestamps(expr: Expression) -> Expression {
+    match expr {
+        Expression::Concat(exprs) => hoist_shared_timestamp(exprs, Expression::Concat),
+        Expression::Sum(exprs) => hoist_shared_timestamp(exprs, Expression::Sum),
+        Expression::Seq { statements, pipelined } => Expression::Seq {
+            statements: statements.into_iter().map(coalesce_shared_timestamps).collect(),
+            pipelined,
+        },
+        Expression::Let { bindings, expr } => Expression::Let {
+            bindings: bindings
+                .into_iter()
+                .map(|b| Binding::new(b.name, coalesce_shared_timestamps(b.expr)))
+                .collect(),
+            expr: Box::new(coalesce_shared_timestamps(*expr)),
+        },
+        other => other,
+    }
+}
+
+fn hoist_shared_timestamp(exprs: Vec<Expression>, rebuild: impl FnOnce(Vec<Expression>) -> Expression) -> Expression {
+    if exprs.len() < 2 {
+        return rebuild(exprs);
+    }
+
+    let params: Vec<&[PrismaValue]> = exprs.iter().map(query_params).collect();
+    let Some(shared) = find_shared_timestamp(&params) else {
+        return rebuild(exprs);
+    };
+
+    let binding_name = "@sharedTimestamp".to_string();
+    let placeholder = PrismaValue::Placeholder {
+        name: binding_name.clone(),
+        r#type: PlaceholderType::Date,
+    };
+
+    let rewritten = exprs
+        .into_iter()
+        .map(|expr| replace_param(expr, &shared, &placeholder))
+        .collect();
+
+    Expression::Let {
+        bindings: vec![Binding::new(binding_name, Expression::Value(shared))],
+        expr: Box::new(rebuild(rewritten)),
+    }
+}
+
+fn query_params(expr: &Expression) -> &[PrismaValue] {
+    match expr {
+        Expression::Query(q) | Expression::Execute(q) => &q.params,
+        _ => &[],
+    }
+}
+
+/// A value is eligible for hoisting when it's a concrete (non-placeholder)
+/// `DateTime` that shows up, unchanged, in every sibling's parameter list.
+fn find_shared_timestamp(params: &[&[PrismaValue]]) -> Option<PrismaValue> {
+    let (first, rest) = params.split_first()?;
+
+    first
+        .iter()
+        .filter(|v| matches!(v, PrismaValue::DateTime(_)))
+        .find(|candidate| rest.iter().all(|other| other.contains(candidate)))
+        .cloned()
+}
+
+fn replace_param(expr: Expression, needle: &PrismaValue, placeholder: &PrismaValue) -> Expression {
+    match expr {
+        Expression::Query(mut q) => {
+            q.params.iter_mut().for_each(|p| {
+                if p == needle {
+                    *p = placeholder.clone();
+                }
+            });
+            Expression::Query(q)
+        }
+        Expression::Execute(mut q) => {
+            q.params.iter_mut().for_each(|p| {
+                if p == needle {
+                    *p = placeholder.clone();
+                }
+            });
+            Expression::Execute(q)
+        }
+        other => other,
+    }
+}
+
+/// A transaction assembled from otherwise-independent statements (e.g. a
+/// JSON batch) often repeats the same constant across most of them anyway —
+/// a tenant id scoping every statement's `WHERE`, or a client-generated uuid
+/// reused as a foreign key. Unlike [`coalesce_shared_timestamps`], which only
+/// looks within the rows of a single batched write, this looks across a
+/// transaction's independent top-level statements and, when it finds a
+/// constant recurring in enough of them (see [`find_shared_constant`]), hoists it into one top-of-transaction
+/// binding. That guarantees every statement sees the exact same value and
+/// shrinks the transaction's overall param set.
+pub(crate) fn hoist_transaction_constants(expr: Expression) -> Expression {
+    let Expression::Seq { statements, pipelined } = expr else {
+        return expr;
+    };
+
+    if statements.len() < 2 {
+        return Expression::Seq { statements, pipelined };
+    }
+
+    let params_per_statement: Vec<Vec<PrismaValue>> = statements
+        .iter()
+        .map(|statement| {
+            let mut params = Vec::new();
+            collect_all_params(statement, &mut params);
+            params
+        })
+        .collect();
+
+    let Some(shared) = find_shared_constant(&params_per_statement) else {
+        return Expression::Seq { statements, pipelined };
+    };
+
+    let binding_name = "@txConstant".to_string();
+    let placeholder = PrismaValue::Placeholder {
+        name: binding_name.clone(),
+        r#type: PlaceholderType::String,
+    };
+
+    let rewritten = statements
+        .into_iter()
+        .map(|statement| replace_param_everywhere(statement, &shared, &placeholder))
+        .collect();
+
+    Expression::Let {
+        bindings: vec![Binding::new(binding_name, Expression::Value(shared))],
+        expr: Box::new(Expression::Seq {
+            statements: rewritten,
+            pipelined,
+        }),
+    }
+}
+
+fn collect_all_params(expr: &Expression, out: &mut Vec<PrismaValue>) {
+    match expr {
+        Expression::Query(q) | Expression::Execute(q) => out.extend(q.params.iter().cloned()),
+        Expression::Seq { statements, .. } => statements.iter().for_each(|e| collect_all_params(e, out)),
+        Expression::Sum(exprs) | Expression::Concat(exprs) => {
+            exprs.iter().for_each(|e| collect_all_params(e, out));
+        }
+        Expression::Let { bindings, expr } => {
+            bindings.iter().for_each(|b| collect_all_params(&b.expr, out));
+            collect_all_params(expr, out);
+        }
+        Expression::Reverse(e) | Expression::Unique(e) | Expression::Columnar(e) => collect_all_params(e, out),
+        Expression::Required { expr, .. } => collect_all_params(expr, out),
+        Expression::Join { parent, children } => {
+            collect_all_params(parent, out);
+            children.iter().for_each(|join| collect_all_params(&join.child, out));
+        }
+        Expression::MapField { records, .. } => collect_all_params(records, out),
+        Expression::Get { .. } | Expression::GetFirstNonEmpty { .. } | Expression::Value(_) => {}
+    }
+}
+
+/// A value is eligible for hoisting when it's a concrete `String` or `Uuid`
+/// (the shapes a tenant id or client-generated id tend to take) that shows up
+/// in at least `len / 2` (rounded down) of the transaction's statements, and
+/// in never fewer than two — frequent enough to be clearly a shared
+/// identifier rather than a coincidental match.
+fn find_shared_constant(params_per_statement: &[Vec<PrismaValue>]) -> Option<PrismaValue> {
+    let threshold = (params_per_statement.len() / 2).max(2);
+
+    params_per_statement
+        .iter()
+        .flatten()
+        .filter(|v| matches!(v, PrismaValue::String(_) | PrismaValue::Uuid(_)))
+        .unique()
+        .find(|candidate| {
+            params_per_statement
+                .iter()
+                .filter(|params| params.contains(candidate))
+                .count()
+                >= threshold
+        })
+        .cloned()
+}
+
+fn replace_param_everywhere(expr: Expression, needle: &PrismaValue, placeholder: &PrismaValue) -> Expression {
+    match expr {
+        Expression::Query(mut q) => {
+            q.params.iter_mut().for_each(|p| {
+                if p == needle {
+                    *p = placeholder.clone();
+                }
+            });
+            Expression::Query(q)
+        }
+        Expression::Execute(mut q) => {
+            q.params.iter_mut().for_each(|p| {
+                if p == needle {
+                    *p = placeholder.clone();
+                }
+            });
+            Expression::Execute(q)
+        }
+        Expression::Seq { statements, pipelined } => Expression::Seq {
+            statements: statements
+                .into_iter()
+                .map(|e| replace_param_everywhere(e, needle, placeholder))
+                .collect(),
+            pipelined,
+        },
+        Expression::Sum(exprs) => {
+            Expression::Sum(exprs.into_iter().map(|e| replace_param_everywhere(e, needle, placeholder)).collect())
+        }
+        Expression::Concat(exprs) => {
+            Expression::Concat(exprs.into_iter().map(|e| replace_param_everywhere(e, needle, placeholder)).collect())
+        }
+        Expression::Let { bindings, expr } => Expression::Let {
+            bindings: bindings
+                .into_iter()
+                .map(|b| Binding::new(b.name, replace_param_everywhere(b.expr, needle, placeholder)))
+                .collect(),
+            expr: Box::new(replace_param_everywhere(*expr, needle, placeholder)),
+        },
+        Expression::Reverse(e) => Expression::Reverse(Box::new(replace_param_everywhere(*e, needle, placeholder))),
+        Expression::Unique(e) => Expression::Unique(Box::new(replace_param_everywhere(*e, needle, placeholder))),
+        Expression::Required { expr, message } => Expression::Required {
+            expr: Box::new(replace_param_everywhere(*expr, needle, placeholder)),
+            message,
+        },
+        Expression::Columnar(e) => Expression::Columnar(Box::new(replace_param_everywhere(*e, needle, placeholder))),
+        Expression::Join { parent, children } => Expression::Join {
+            parent: Box::new(replace_param_everywhere(*parent, needle, placeholder)),
+            children: children
+                .into_iter()
+                .map(|join| JoinExpression {
+                    child: replace_param_everywhere(join.child, needle, placeholder),
+                    ..join
+                })
+                .collect(),
+        },
+        Expression::MapField { field, records } => Expression::MapField {
+            field,
+            records: Box::new(replace_param_everywhere(*records, needle, placeholder)),
+        },
+        other => other,
+    }
+}
+
+/// Nested result folding can bind a `GetFirstNonEmpty` under a name that's
+/// itself referenced by another `GetFirstNonEmpty`. This flattens such chains
+/// into a single `GetFirstNonEmpty` over the concatenated, priority-ordered
+/// list of names, so the interpreter only ever has to resolve one level of
+/// indirection.
+fn flatten_nested_get_first_non_empty(expr: Expression) -> Expression {
+    let bindings = collect_get_first_non_empty_bindings(&expr);
+    if bindings.is_empty() {
+        return expr;
+    }
+
+    rewrite_get_first_non_empty(expr, &bindings)
+}
+
+fn collect_get_first_non_empty_bindings(expr: &Expression) -> HashMap<String, Vec<String>> {
+    let mut bindings = HashMap::new();
+    collect_bindings(expr, &mut bindings);
+    bindings
+}
+
+fn collect_bindings(expr: &Expression, bindings: &mut HashMap<String, Vec<String>>) {
+    match expr {
+        Expression::Seq { statements, .. } => statements.iter().for_each(|e| collect_bindings(e, bindings)),
+        Expression::Sum(exprs) | Expression::Concat(exprs) => {
+            exprs.iter().for_each(|e| collect_bindings(e, bindings));
+        }
+        Expression::Let { bindings: lets, expr } => {
+            for binding in lets {
+                if let Expression::GetFirstNonEmpty { names } = &binding.expr {
+                    bindings.insert(binding.name.clone(), names.clone());
+                }
+                collect_bindings(&binding.expr, bindings);
+            }
+            collect_bindings(expr, bindings);
+        }
+        Expression::Reverse(e) | Expression::Unique(e) | Expression::Columnar(e) => collect_bindings(e, bindings),
+        Expression::Required { expr, .. } => collect_bindings(expr, bindings),
+        Expression::Join { parent, children } => {
+            collect_bindings(parent, bindings);
+            children.iter().for_each(|join| collect_bindings(&join.child, bindings));
+        }
+        Expression::MapField { records, .. } => collect_bindings(records, bindings),
+        Expression::Get { .. }
+        | Expression::GetFirstNonEmpty { .. }
+        | Expression::Query(_)
+        | Expression::Execute(_)
+        | Expression::Value(_) => {}
+    }
+}
+
+fn rewrite_get_first_non_empty(expr: Expression, bindings: &HashMap<String, Vec<String>>) -> Expression {
+    match expr {
+        Expression::GetFirstNonEmpty { names } => Expression::GetFirstNonEmpty {
+            names: flatten_names(&names, bindings),
+        },
+        Expression::Seq { statements, pipelined } => Expression::Seq {
+            statements: statements
+                .into_iter()
+                .map(|e| rewrite_get_first_non_empty(e, bindings))
+                .collect(),
+            pipelined,
+        },
+        Expression::Sum(exprs) => {
+            Expression::Sum(exprs.into_iter().map(|e| rewrite_get_first_non_empty(e, bindings)).collect())
+        }
+        Expression::Concat(exprs) => {
+            Expression::Concat(exprs.into_iter().map(|e| rewrite_get_first_non_empty(e, bindings)).collect())
+        }
+        Expression::Let { bindings: lets, expr } => Expression::Let {
+            bindings: lets
+                .into_iter()
+                .map(|b| Binding::new(b.name, rewrite_get_first_non_empty(b.expr, bindings)))
+                .collect(),
+            expr: Box::new(rewrite_get_first_non_empty(*expr, bindings)),
+        },
+        Expression::Reverse(e) => Expression::Reverse(Box::new(rewrite_get_first_non_empty(*e, bindings))),
+        Expression::Unique(e) => Expression::Unique(Box::new(rewrite_get_first_non_empty(*e, bindings))),
+        Expression::Required { expr, message } => Expression::Required {
+            expr: Box::new(rewrite_get_first_non_empty(*expr, bindings)),
+            message,
+        },
+        Expression::Columnar(e) => Expression::Columnar(Box::new(rewrite_get_first_non_empty(*e, bindings))),
+        Expression::Join { parent, children } => Expression::Join {
+            parent: Box::new(rewrite_get_first_non_empty(*parent, bindings)),
+            children: children
+                .into_iter()
+                .map(|join| JoinExpression {
+                    child: rewrite_get_first_non_empty(join.child, bindings),
+                    ..join
+                })
+                .collect(),
+        },
+        Expression::MapField { field, records } => Expression::MapField {
+            field,
+            records: Box::new(rewrite_get_first_non_empty(*records, bindings)),
+        },
+        other => other,
+    }
+}
+
+/// Resolves each name in `names` against `bindings`, splicing in a bound
+/// `GetFirstNonEmpty`'s own names (recursively) in place of the name that
+/// pointed to it, and keeping everything else as-is. Preserves priority
+/// order: a flattened name list still tries its entries left to right.
+fn flatten_names(names: &[String], bindings: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut result = Vec::with_capacity(names.len());
+
+    for name in names {
+        match bindings.get(name) {
+            Some(nested) => result.extend(flatten_names(nested, bindings)),
+            None => result.push(name.clone()),
+        }
+    }
+
+    result
+}
+
+/// Appends an explicit type cast after every positional `$N` parameter whose type is statically
+/// known and `builder` has a cast for (see [`QueryBuilder::placeholder_cast`]), for
+/// `CompileOptions::explicit_casts`.
+pub(crate) fn annotate_placeholder_casts(expr: Expression, builder: &dyn QueryBuilder) -> Expression {
+    match expr {
+        Expression::Query(q) => Expression::Query(cast_placeholders(q, builder)),
+        Expression::Execute(q) => Expression::Execute(cast_placeholders(q, builder)),
+        Expression::Seq { statements, pipelined } => Expression::Seq {
+            statements: statements.into_iter().map(|e| annotate_placeholder_casts(e, builder)).collect(),
+            pipelined,
+        },
+        Expression::Sum(exprs) => {
+            Expression::Sum(exprs.into_iter().map(|e| annotate_placeholder_casts(e, builder)).collect())
+        }
+        Expression::Concat(exprs) => {
+            Expression::Concat(exprs.into_iter().map(|e| annotate_placeholder_casts(e, builder)).collect())
+        }
+        Expression::Let { bindings, expr } => Expression::Let {
+            bindings: bindings
+                .into_iter()
+                .map(|b| Binding::new(b.name, annotate_placeholder_casts(b.expr, builder)))
+                .collect(),
+            expr: Box::new(annotate_placeholder_casts(*expr, builder)),
+        },
+        Expression::Reverse(e) => Expression::Reverse(Box::new(annotate_placeholder_casts(*e, builder))),
+        Expression::Unique(e) => Expression::Unique(Box::new(annotate_placeholder_casts(*e, builder))),
+        Expression::Required { expr, message } => Expression::Required {
+            expr: Box::new(annotate_placeholder_casts(*expr, builder)),
+            message,
+        },
+        Expression::Columnar(e) => Expression::Columnar(Box::new(annotate_placeholder_casts(*e, builder))),
+        Expression::Join { parent, children } => Expression::Join {
+            parent: Box::new(annotate_placeholder_casts(*parent, builder)),
+            children: children
+                .into_iter()
+                .map(|join| JoinExpression {
+                    child: annotate_placeholder_casts(join.child, builder),
+                    ..join
+                })
+                .collect(),
+        },
+        Expression::MapField { field, records } => Expression::MapField {
+            field,
+            records: Box::new(annotate_placeholder_casts(*records, builder)),
+        },
+        other @ (Expression::Get { .. } | Expression::GetFirstNonEmpty { .. } | Expression::Value(_)) => other,
+    }
+}
+
+/// Rewrites `query.query`'s `$1`, `$2`, ... parameters in place, appending `::cast` after each
+/// `$N` whose matching `query.params[N - 1]` is a symbolic [`PrismaValue::Placeholder`] (a
+/// concrete literal has no statically known type at this layer to cast to) and `builder` has a
+/// [`QueryBuilder::placeholder_cast`] for. Only does anything useful on the positional, 1-indexed
+/// `$N` syntax Postgres uses — on connectors without one, every lookup misses and the query comes
+/// back unchanged.
+fn cast_placeholders(mut query: DbQuery, builder: &dyn QueryBuilder) -> DbQuery {
+    let original = std::mem::take(&mut query.query);
+    let mut rewritten = String::with_capacity(original.len());
+    let mut chars = original.char_indices().peekable();
+
+    while let Some((_, ch)) = chars.next() {
+        rewritten.push(ch);
+        if ch != '$' {
+            continue;
+        }
+
+        let Some(&(digits_start, _)) = chars.peek() else {
+            continue;
+        };
+        let mut digits_end = digits_start;
+        while let Some(&(next_idx, next_ch)) = chars.peek() {
+            if !next_ch.is_ascii_digit() {
+                break;
+            }
+            digits_end = next_idx + next_ch.len_utf8();
+            chars.next();
+        }
+        if digits_end == digits_start {
+            continue;
+        }
+
+        let digits = &original[digits_start..digits_end];
+        rewritten.push_str(digits);
+
+        let index: usize = digits.parse().expect("scanned only ASCII digits");
+        let Some(cast) = query
+            .params
+            .get(index.wrapping_sub(1))
+            .and_then(placeholder_type)
+            .and_then(|r#type| builder.placeholder_cast(r#type))
+        else {
+            continue;
+        };
+        rewritten.push_str("::");
+        rewritten.push_str(&cast);
+    }
+
+    query.query = rewritten;
+    query
+}
+
+fn placeholder_type(value: &PrismaValue) -> Option<&PlaceholderType> {
+    match value {
+        PrismaValue::Placeholder { r#type, .. } => Some(r#type),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flattens_a_nested_get_first_non_empty_chain_in_priority_order() {
+        // outer = firstNonEmpty(a, inner), inner = firstNonEmpty(b, c)
+        let expr = Expression::Let {
+            bindings: vec![
+                Binding::new("inner".to_string(), Expression::GetFirstNonEmpty {
+                    names: vec!["b".to_string(), "c".to_string()],
+                }),
+                Binding::new("outer".to_string(), Expression::GetFirstNonEmpty {
+                    names: vec!["a".to_string(), "inner".to_string()],
+                }),
+            ],
+            expr: Box::new(Expression::Get {
+                name: "outer".to_string(),
+            }),
+        };
+
+        let Expression::Let { bindings, .. } = flatten_nested_get_first_non_empty(expr) else {
+            panic!("expected a Let");
+        };
+
+        let outer = bindings.into_iter().find(|b| b.name == "outer").unwrap();
+        let Expression::GetFirstNonEmpty { names } = outer.expr else {
+            panic!("expected outer binding to stay a GetFirstNonEmpty");
+        };
+
+        assert_eq!(names, vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    }
+}
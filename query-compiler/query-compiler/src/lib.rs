@@ -1,23 +1,37 @@
+//! Compiles a [`query_core::QueryGraph`] into an [`Expression`] plan.
+//!
+//! This crate only targets SQL connectors: [`compile_with_options`] dispatches
+//! on [`quaint::prelude::SqlFamily`], which has no Mongo variant, and the only
+//! [`query_builder::QueryBuilder`] implementation in the workspace is the
+//! quaint-backed [`sql_query_builder::SqlQueryBuilder`] (see the `Cargo.toml`
+//! comment on the `sql-query-builder`/`quaint` dependencies). Mongo queries
+//! still run through the legacy interpreter and its own connector crate;
+//! giving them a compiled plan would mean writing a Mongo `QueryBuilder` and
+//! widening `compile_with_options`'s dispatch first.
+
 pub mod expression;
+pub mod metrics;
 pub mod translate;
 
 use std::sync::Arc;
 
 pub use expression::Expression;
+pub use metrics::CompileMetrics;
 use quaint::{
     prelude::{ConnectionInfo, SqlFamily},
     visitor,
 };
-use query_core::{schema::QuerySchema, QueryGraphBuilderError};
+pub use query_builder::LockMode;
+use query_core::{query_document::BatchDocument, schema::QuerySchema, QueryGraphBuilderError};
 use sql_query_builder::{Context, SqlQueryBuilder};
 use thiserror::Error;
-pub use translate::{translate, TranslateError};
+pub use translate::{translate, translate_batch, translate_subgraph, CompileOptions, TranslateError};
 
 use query_core::{QueryDocument, QueryGraphBuilder};
 
 #[derive(Debug, Error)]
 pub enum CompileError {
-    #[error("only a single query can be compiled at a time")]
+    #[error("compacted batch queries cannot be compiled, only single queries and `$transaction` batches")]
     UnsupportedRequest,
 
     #[error("failed to build query graph: {0}")]
@@ -32,21 +46,36 @@ pub fn compile(
     query_doc: QueryDocument,
     connection_info: &ConnectionInfo,
 ) -> Result<Expression, CompileError> {
-    let QueryDocument::Single(query) = query_doc else {
-        return Err(CompileError::UnsupportedRequest);
+    compile_with_options(query_schema, query_doc, connection_info, &CompileOptions::default())
+}
+
+pub fn compile_with_options(
+    query_schema: &Arc<QuerySchema>,
+    query_doc: QueryDocument,
+    connection_info: &ConnectionInfo,
+    options: &CompileOptions,
+) -> Result<Expression, CompileError> {
+    let queries = match query_doc {
+        QueryDocument::Single(query) => vec![query],
+        QueryDocument::Multi(BatchDocument::Multi(queries, _transaction)) => queries,
+        QueryDocument::Multi(BatchDocument::Compact(_)) => return Err(CompileError::UnsupportedRequest),
     };
 
     let ctx = Context::new(connection_info, None);
-    let (graph, _serializer) = QueryGraphBuilder::new(query_schema).build(query)?;
+    let graphs = queries
+        .into_iter()
+        .map(|query| QueryGraphBuilder::new(query_schema).build(query).map(|(graph, _)| graph))
+        .collect::<Result<Vec<_>, _>>()?;
+
     let res: Result<Expression, TranslateError> = match connection_info.sql_family() {
         #[cfg(feature = "postgresql")]
-        SqlFamily::Postgres => translate(graph, &SqlQueryBuilder::<visitor::Postgres<'_>>::new(ctx)),
+        SqlFamily::Postgres => translate_batch(graphs, &SqlQueryBuilder::<visitor::Postgres<'_>>::new(ctx), options),
         #[cfg(feature = "mysql")]
-        SqlFamily::Mysql => translate(graph, &SqlQueryBuilder::<visitor::Mysql<'_>>::new(ctx)),
+        SqlFamily::Mysql => translate_batch(graphs, &SqlQueryBuilder::<visitor::Mysql<'_>>::new(ctx), options),
         #[cfg(feature = "sqlite")]
-        SqlFamily::Sqlite => translate(graph, &SqlQueryBuilder::<visitor::Sqlite<'_>>::new(ctx)),
+        SqlFamily::Sqlite => translate_batch(graphs, &SqlQueryBuilder::<visitor::Sqlite<'_>>::new(ctx), options),
         #[cfg(feature = "mssql")]
-        SqlFamily::Mssql => translate(graph, &SqlQueryBuilder::<visitor::Mssql<'_>>::new(ctx)),
+        SqlFamily::Mssql => translate_batch(graphs, &SqlQueryBuilder::<visitor::Mssql<'_>>::new(ctx), options),
     };
 
     res.map_err(CompileError::TranslateError)
@@ -0,0 +1,56 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// Feature-coverage counts collected while compiling a [`QueryGraph`](query_core::QueryGraph), for
+/// feeding a rollout dashboard that tracks which engine features the graphs produced by a given
+/// client version actually exercise. Collection only happens when
+/// [`CompileOptions::metrics`](super::translate::CompileOptions::metrics) is set, so a caller that
+/// doesn't ask for it pays nothing beyond the `Option` check at each recording site.
+#[derive(Debug, Default, Clone)]
+pub struct CompileMetrics {
+    /// Occurrences of each [`QueryGraphDependency`](query_core::QueryGraphDependency) variant
+    /// resolved while compiling, keyed by its variant name (e.g. `"ProjectedDataDependency"`).
+    pub dependency_kinds: HashMap<&'static str, u64>,
+
+    /// Occurrences of each [`Query`](query_core::Query) variant translated, keyed by its variant
+    /// name (e.g. `"DeleteRecord"`).
+    pub query_kinds: HashMap<&'static str, u64>,
+
+    /// Occurrences of each builder capability the plan relied on, keyed by name (e.g.
+    /// `"pipelining"`, `"defer_constraints"`).
+    pub capabilities_used: HashMap<&'static str, u64>,
+
+    /// Human-readable notices about compromises the compiler made to produce a plan it could
+    /// still run, e.g. clamping a `take` that exceeded the builder's [`max_limit`] instead of
+    /// sending a `LIMIT` the connector would reject. Unlike [`TranslateError`], these don't stop
+    /// compiling — they're here for a caller that wants to surface them (logs, a lint in
+    /// development) without changing behavior.
+    ///
+    /// [`max_limit`]: query_builder::QueryBuilder::max_limit
+    /// [`TranslateError`]: super::translate::TranslateError
+    pub warnings: Vec<String>,
+}
+
+impl CompileMetrics {
+    pub(crate) fn record_dependency(&mut self, kind: &'static str) {
+        *self.dependency_kinds.entry(kind).or_default() += 1;
+    }
+
+    pub(crate) fn record_query(&mut self, kind: &'static str) {
+        *self.query_kinds.entry(kind).or_default() += 1;
+    }
+
+    pub(crate) fn record_capability(&mut self, kind: &'static str) {
+        *self.capabilities_used.entry(kind).or_default() += 1;
+    }
+
+    pub(crate) fn record_warning(&mut self, message: String) {
+        self.warnings.push(message);
+    }
+}
+
+/// A shared handle to a [`CompileMetrics`] accumulator, so the caller that passed it into
+/// [`CompileOptions`](super::translate::CompileOptions) keeps a reference it can read once
+/// compiling finishes. Cloning a [`CompileOptions`] (e.g. once per graph in
+/// [`translate_batch`](super::translate::translate_batch)) shares the same accumulator rather than
+/// resetting it, so counts aggregate across the whole batch.
+pub type MetricsHandle = Rc<RefCell<CompileMetrics>>;
@@ -1,13 +1,23 @@
+mod filter_optimize;
 mod query;
 
+use std::collections::HashMap;
+
 use itertools::{Either, Itertools};
 use query::translate_query;
-use query_builder::QueryBuilder;
-use query_core::{EdgeRef, Node, NodeRef, Query, QueryGraph, QueryGraphBuilderError, QueryGraphDependency};
-use query_structure::{PlaceholderType, PrismaValue, SelectedField, SelectionResult};
+use query_builder::{LockMode, QueryBuilder};
+use query_core::{
+    EdgeRef, Node, NodeRef, Query, QueryGraph, QueryGraphBuilderError, QueryGraphDependency, ReadQuery, UpdateRecord,
+    WriteQuery,
+};
+use query_structure::{
+    psl, FieldSelection, Filter, PlaceholderType, PrismaValue, ScalarWriteOperation, SelectedField, SelectionResult,
+    WriteArgs,
+};
 use thiserror::Error;
 
 use super::expression::{Binding, Expression};
+use super::metrics::MetricsHandle;
 
 #[derive(Debug, Error)]
 pub enum TranslateError {
@@ -19,17 +29,460 @@ pub enum TranslateError {
 
     #[error("query graph build error: {0}")]
     GraphBuildError(#[from] QueryGraphBuilderError),
+
+    #[error("cannot write to `{0}`: it's a view, which is read-only")]
+    WriteToView(String),
+
+    #[error("node {0} not found")]
+    NodeNotFound(String),
+
+    #[error("node {0} depends on node(s) outside of its subgraph: {1:?}")]
+    DanglingSubgraphDependency(String, Vec<String>),
+
+    #[error("plan cost {estimated} exceeds the configured limit of {max_cost}")]
+    CostLimitExceeded { estimated: u64, max_cost: u64 },
+
+    #[error("conflicting input for `{model}.{field}`: it's set directly and also targeted by a relation connect")]
+    ConflictingRelationInput { model: String, field: String },
+
+    #[error("cannot write to `{field}`: it's computed by the database (`@default(dbgenerated(..))`)")]
+    WriteToGeneratedColumn { field: String },
 }
 
 pub type TranslateResult<T> = Result<T, TranslateError>;
 
-pub fn translate(mut graph: QueryGraph, builder: &dyn QueryBuilder) -> TranslateResult<Expression> {
-    graph
+/// Options governing how a [`QueryGraph`] is compiled into an [`Expression`].
+/// Every field defaults to the behavior of the engine before that option was
+/// introduced, so `CompileOptions::default()` is always a safe choice.
+#[derive(Debug, Default, Clone)]
+pub struct CompileOptions {
+    /// Rejects plans whose [`estimated_cost`] exceeds this limit, to protect
+    /// shared databases from abusive queries.
+    pub max_cost: Option<u64>,
+
+    /// Has `createMany` skip rows that would otherwise violate a unique
+    /// constraint instead of erroring, so re-running the same `createMany` is
+    /// safe. Other writes compile unchanged.
+    pub idempotent: bool,
+
+    /// Wraps the compiled plan's result in [`Expression::Columnar`], hinting
+    /// to the interpreter that it should group the returned rows by column
+    /// (e.g. to hand them to an Arrow-backed consumer) instead of returning
+    /// one record at a time.
+    pub columnar: bool,
+
+    /// Only read by [`translate_batch`]: wraps each operation in its own
+    /// savepoint, so a host running the batch can roll back a single failing
+    /// operation instead of aborting the whole transaction.
+    pub savepoint_per_operation: bool,
+
+    /// Emits a statement at the start of the plan that defers checking of
+    /// the transaction's deferrable constraints (e.g. foreign keys) until
+    /// commit time, instead of after each statement. This lets a batch of
+    /// writes with circular foreign key dependencies commit in any order,
+    /// at the cost of surfacing constraint violations later than usual.
+    pub defer_constraints: bool,
+
+    /// Locks the rows a top-level read query returns, e.g. with `SELECT ...
+    /// FOR UPDATE`, so a concurrent transaction can't modify them until this
+    /// one ends. Silently has no effect on connectors without row-level
+    /// locking, and on nested reads performed to fill in a relation. Only
+    /// useful inside a transaction; this crate has no notion of whether one
+    /// is open, so requesting a lock outside of one is the caller's mistake
+    /// to avoid.
+    pub lock_mode: Option<LockMode>,
+
+    /// Caps an otherwise-unbounded top-level `findMany` at `max_rows + 1`
+    /// rows, so a caller streaming the result can tell "there were more rows
+    /// than `max_rows`" apart from "there were exactly `max_rows`" and error
+    /// instead of quietly streaming millions of rows. This is a safety net
+    /// distinct from user-requested pagination: a query that already has its
+    /// own `take` is left untouched, however large.
+    pub max_rows: Option<usize>,
+
+    /// Only read by [`translate_batch`]: sets the `pipelined` hint on the batch's top-level
+    /// [`Expression::Seq`] when it holds more than one operation and `builder` also supports it,
+    /// so an interpreter talking to a connector with multi-statement pipelining (e.g. Postgres's
+    /// extended query protocol) can send them in one round trip instead of waiting for each
+    /// response before issuing the next. `Seq` nodes inside a single compiled plan never carry it.
+    pub pipelined: bool,
+
+    /// When set, compiling accumulates feature-coverage counts (dependency kinds resolved, query
+    /// types translated, builder capabilities relied on) into this handle as a side effect, for
+    /// feeding a rollout dashboard that tracks which engine features graphs exercise. Left `None`
+    /// this costs nothing beyond the `Option` check at each recording site.
+    pub metrics: Option<MetricsHandle>,
+
+    /// Only read by [`translate_batch`]: when every operation in the batch is a single,
+    /// independent write (no nested operations of its own), issues them against the database in a
+    /// canonical order — by target table, then by a stable tiebreaker derived from the write's own
+    /// filter/args — instead of the caller's order. Concurrent transactions that acquire their row
+    /// and table locks in the same order are less likely to deadlock with each other. The batch's
+    /// visible results are unaffected: they're still handed back in the caller's original order,
+    /// only the order statements are issued to the database changes. Left `false` (or as soon as
+    /// one operation isn't a plain independent write) the batch runs in caller order, as before.
+    /// Overrides [`Self::pipelined`] when both are set and reordering actually applies: the
+    /// reordered writes are threaded through named bindings to preserve the caller's result order,
+    /// which have no `pipelined` hint of their own, so they're always issued one at a time
+    /// regardless of what `pipelined` asks for. Recorded as a warning on `metrics` when it happens.
+    pub canonical_write_order: bool,
+
+    /// Appends an explicit type cast after every SQL parameter whose type is known at compile
+    /// time and `builder` has a cast for (see [`QueryBuilder::placeholder_cast`]), e.g. emitting
+    /// `$1::int4` instead of `$1`. Some driver adapters — JS drivers fronting a connector they
+    /// don't speak the native wire protocol of are the common case — can't infer a parameter's
+    /// type the way a native driver can, and fail with "could not determine data type of
+    /// parameter" unless it's annotated. In practice this only reaches the symbolic parameters
+    /// used to thread one compiled query's result into a later one in the same plan (e.g. a
+    /// nested create's parent-id foreign key): a literal value sent by the client compiles
+    /// straight into the query with its own concrete type already inferable by the driver from
+    /// the value itself, so there's nothing this plan-level pass can usefully add for it. Left
+    /// `false`, placeholders are emitted bare, as before.
+    pub explicit_casts: bool,
+
+    /// For `createManyAndReturn`, compiles each row as its own single-row insert (reusing the
+    /// same builder method plain `create` uses) instead of batching several rows into one
+    /// multi-row `INSERT ... RETURNING`, so the rows come back in the caller's insertion order.
+    /// A multi-row `RETURNING` doesn't guarantee its rows come back in the order their `VALUES`
+    /// were listed on every connector, which silently scrambles the result of an otherwise
+    /// order-sensitive `createManyAndReturn`. Left `false`, rows are batched as before, and
+    /// their order is whatever the connector's `RETURNING` happens to produce. Has no effect
+    /// when `skip_duplicates` is also requested: skipping conflicting rows relies on the
+    /// batched insert's `ON CONFLICT DO NOTHING`, which the single-row path can't express.
+    pub preserve_insertion_order: bool,
+
+    /// Overrides the message carried by a `*OrThrow` operation's not-found failure (see
+    /// [`Expression::Required`]), in place of the engine's standard "Expected a record, found
+    /// none.". `{model}` in the template is replaced with the queried model's name, e.g.
+    /// `Some("No {model} matched your query".to_owned())` for `findUniqueOrThrow` on `Post`
+    /// produces "No Post matched your query". Left `None`, the standard message is used,
+    /// unchanged.
+    pub not_found_message: Option<String>,
+
+    /// Adds a total-count column to a top-level `findMany`'s selection (a windowed `COUNT(*)
+    /// OVER()`, or a count over the unpaginated rows when the query has `distinct`; see
+    /// [`query_builder::TOTAL_COUNT_ALIAS`] for the column alias it's reported under), so the
+    /// plan can hand back how many rows matched the query's filter in total alongside the
+    /// `take`/`skip`-limited page it actually returns, without a second round trip. Left `false`,
+    /// the query returns only the page, as before. `findFirst` compiles to the same many-records
+    /// read and gets the column too. Has no effect on `findUnique` or nested reads.
+    pub include_total_count: bool,
+
+    /// A filter ANDed into every read of the named model, keyed by model name (not the model type
+    /// itself, which has no [`std::hash::Hash`] impl to key a map with). Meant for soft deletes:
+    /// `{"Post": deletedAt_is_null_filter}` excludes soft-deleted posts from a `findMany` on
+    /// `Post` just as much as from an `include: { posts }` read nested under some other model —
+    /// the filter is applied wherever `Post` rows are read, not only at the top level, since a
+    /// caller who can't see a model's soft-deleted rows directly shouldn't see them show up
+    /// through a relation either. Left empty, no model gets an implicit filter, as before.
+    pub soft_delete_filters: HashMap<String, Filter>,
+
+    /// The preview features enabled for the schema being compiled, e.g. to let `translate` pick a
+    /// feature-gated lowering only when its feature is turned on, and error clearly instead of
+    /// silently emitting wrong SQL when a plan needs one that isn't. As things stand this compiler
+    /// has no lowering that actually branches on it: every query-shape choice it makes today
+    /// (nested reads always compile to a separate query and stitch results together in-memory,
+    /// `distinct` always lowers to a native `DISTINCT ON`) is a fixed property of this compiler,
+    /// not a preview-feature-driven pick between a native path and a fallback one — the one
+    /// feature-gated alternative that does exist, joined nested reads, lives entirely in the
+    /// legacy interpreter's `sql-query-connector` crate and is selected before a plan ever reaches
+    /// this compiler, not by it. Left at its default (no features enabled), compiling behaves
+    /// exactly as before this field existed; it's accepted now so a caller already has
+    /// somewhere to put the schema's enabled features once a lowering here needs to consult them.
+    pub enabled_preview_features: psl::PreviewFeatures,
+
+    /// The declared partition/shard key for each partitioned model, keyed by model name, e.g.
+    /// `{"Event": "tenantId"}` for a model whose underlying table is partitioned on `tenantId`.
+    /// A top-level `findMany` on a partitioned model whose filter doesn't constrain the shard key
+    /// records a warning on [`CompileOptions::metrics`] (full-scan risk: the database can't prune
+    /// partitions it has no predicate to prune by). When the filter does constrain it, no extra
+    /// work is needed to "place" the predicate for pruning: a partition planner decides whether to
+    /// prune from the predicate's presence in the `WHERE` clause, not its position within it, and
+    /// every shard-key condition here is an ordinary scalar filter already emitted there. Left
+    /// empty, no model is treated as partitioned and no warning is ever recorded, as before.
+    pub shard_keys: HashMap<String, String>,
+
+    /// Extra columns to add to a write's `RETURNING`, keyed by model name (not the model type
+    /// itself, which has no [`std::hash::Hash`] impl to key a map with), for a change-data-capture
+    /// subscriber that needs more than what the caller selected to publish a change event. Applies
+    /// to every write that can carry a `RETURNING`-shaped selection — `create`, `createMany`,
+    /// `update`, `updateMany`, `upsert`, and `delete` — since a CDC subscriber cares about updates
+    /// and deletes at least as much as inserts. Any field already in the caller's selection is left
+    /// alone rather than duplicated. These extra columns come back in the same record a normal
+    /// selection would — this compiler's plan has no construct for splitting one query's result
+    /// into a client-visible part and a side channel — so a caller that doesn't want them reaching
+    /// the client end of the wire needs to strip them back out of the row by name (the same way
+    /// [`query_builder::TOTAL_COUNT_ALIAS`] is stripped out of a read's row) before handing the
+    /// record back. Left empty, a write selects exactly what the caller asked for, as before.
+    pub cdc_columns: HashMap<String, Vec<String>>,
+}
+
+pub fn translate(
+    mut graph: QueryGraph,
+    builder: &dyn QueryBuilder,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
+    let expr = graph
         .root_nodes()
         .into_iter()
-        .map(|node| NodeTranslator::new(&mut graph, node, &[], builder).translate())
+        .map(|node| NodeTranslator::new(&mut graph, node, &[], builder, options).translate())
         .collect::<TranslateResult<Vec<_>>>()
-        .map(Expression::Seq)
+        .map(|statements| Expression::Seq {
+            statements,
+            pipelined: false,
+        })
+        .map(super::expression::transform::optimize)?;
+
+    let expr = if options.columnar {
+        Expression::Columnar(Box::new(expr))
+    } else {
+        expr
+    };
+
+    if let Some(max_cost) = options.max_cost {
+        let estimated = expr.estimated_cost();
+        if estimated > max_cost {
+            return Err(TranslateError::CostLimitExceeded { estimated, max_cost });
+        }
+    }
+
+    super::expression::lint::warn_on_dangling_writes(&expr);
+
+    let expr = if options.defer_constraints {
+        if let Some(metrics) = &options.metrics {
+            metrics.borrow_mut().record_capability("defer_constraints");
+        }
+        let defer = builder.build_defer_constraints().map_err(TranslateError::QueryBuildFailure)?;
+        Expression::Seq {
+            statements: vec![Expression::Execute(defer), expr],
+            pipelined: false,
+        }
+    } else {
+        expr
+    };
+
+    let expr = if options.explicit_casts {
+        if let Some(metrics) = &options.metrics {
+            metrics.borrow_mut().record_capability("explicit_casts");
+        }
+        super::expression::transform::annotate_placeholder_casts(expr, builder)
+    } else {
+        expr
+    };
+
+    Ok(expr)
+}
+
+/// Compiles only the subtree of `graph` rooted at `root_node_id`, e.g. to
+/// inspect or debug the plan for one branch of a complex nested write in
+/// isolation. Errors if any node below the root depends on a node outside of
+/// the subgraph, since such a dependency couldn't be resolved without the rest
+/// of the graph. The root's own incoming dependencies are neither checked nor
+/// resolved: the root is compiled as if it had none.
+pub fn translate_subgraph(
+    mut graph: QueryGraph,
+    root_node_id: &str,
+    builder: &dyn QueryBuilder,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
+    let root = graph
+        .node_by_id(root_node_id)
+        .ok_or_else(|| TranslateError::NodeNotFound(root_node_id.to_owned()))?;
+
+    let subgraph = graph.subgraph_nodes(&root);
+    let outside_dependencies = subgraph
+        .iter()
+        .filter(|node| **node != root)
+        .flat_map(|node| graph.incoming_edges(node))
+        .map(|edge| graph.edge_source(&edge))
+        .filter(|source| !subgraph.contains(source))
+        .map(|node| node.id())
+        .collect::<Vec<_>>();
+
+    if !outside_dependencies.is_empty() {
+        return Err(TranslateError::DanglingSubgraphDependency(
+            root_node_id.to_owned(),
+            outside_dependencies,
+        ));
+    }
+
+    let expr = NodeTranslator::new(&mut graph, root, &[], builder, options).translate()?;
+
+    if let Some(max_cost) = options.max_cost {
+        let estimated = expr.estimated_cost();
+        if estimated > max_cost {
+            return Err(TranslateError::CostLimitExceeded { estimated, max_cost });
+        }
+    }
+
+    super::expression::lint::warn_on_dangling_writes(&expr);
+
+    Ok(expr)
+}
+
+/// Compiles a `$transaction` batch, i.e. a list of independently-built
+/// [`QueryGraph`]s, into a single [`Expression`] that runs them in order.
+///
+/// Each graph is translated on its own: the JSON protocol (and the
+/// [`QueryGraphBuilder`](query_core::QueryGraphBuilder) that consumes it) has
+/// no way to express "bind this operation's result into a later one", so an
+/// operation that tries to `connect` to a record created earlier in the same
+/// batch can't be threaded through here — it needs an interactive
+/// transaction instead, where the client observes each result before issuing
+/// the next query.
+///
+/// With [`CompileOptions::savepoint_per_operation`], each operation is also
+/// wrapped in its own savepoint. That only covers the compile-time half of
+/// partial-failure tolerance: rolling back to a savepoint after a failed
+/// operation is a runtime decision, and `Expression` has no conditional
+/// branch to express "if this failed, roll back" (see the `Then`/`Else`
+/// dependencies below, which aren't implemented either) — a host wanting
+/// that behavior still has to catch the error and issue the rollback itself.
+///
+/// One optimization this deliberately doesn't do yet: coalescing a batch of
+/// independent `findUnique`s on the same model into a single `WHERE id IN
+/// (...)` query (the classic dataloader trick). Doing that correctly needs an
+/// `Expression` primitive that can take the one shared query result and hand
+/// each original caller back just its own row by key; [`Expression::Join`]
+/// is the closest thing we have, but it attaches children to a parent by key,
+/// not the reverse. Until the IR grows that primitive, each operation keeps
+/// its own query, which is correct, if not maximally efficient.
+pub fn translate_batch(
+    graphs: Vec<QueryGraph>,
+    builder: &dyn QueryBuilder,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
+    let lock_order = if options.canonical_write_order {
+        canonical_write_order(&graphs)
+    } else {
+        None
+    };
+
+    let exprs = graphs
+        .into_iter()
+        .enumerate()
+        .map(|(i, graph)| {
+            let expr = translate(graph, builder, options)?;
+            if !options.savepoint_per_operation {
+                return Ok(expr);
+            }
+
+            let name = format!("batch_op_{i}");
+            let savepoint = builder.build_savepoint(&name).map_err(TranslateError::QueryBuildFailure)?;
+            let release = builder
+                .build_release_savepoint(&name)
+                .map_err(TranslateError::QueryBuildFailure)?;
+            let result_binding = format!("@{name}_result");
+
+            Ok(Expression::Seq {
+                statements: vec![
+                    Expression::Execute(savepoint),
+                    Expression::Let {
+                        bindings: vec![Binding::new(result_binding.clone(), expr)],
+                        expr: Box::new(Expression::Seq {
+                            statements: vec![Expression::Execute(release), Expression::Get { name: result_binding }],
+                            pipelined: false,
+                        }),
+                    },
+                ],
+                pipelined: false,
+            })
+        })
+        .collect::<TranslateResult<Vec<_>>>()?;
+
+    let expr = match lock_order {
+        // Bind every operation under a name in `order` (the order we want the database to see
+        // them in), then hand them back via `Get` in the caller's original order, so reordering
+        // for lock acquisition doesn't reorder the batch's visible results.
+        Some(order) => {
+            // Reordered writes are threaded through named `Let` bindings rather than a `Seq`, so
+            // there's no `pipelined` flag here to set: a caller who also asked for
+            // `options.pipelined` loses it while `canonical_write_order` is in effect, with a
+            // warning recorded on `options.metrics` when that is set.
+            if options.pipelined && exprs.len() > 1 && builder.supports_pipelining() {
+                if let Some(metrics) = &options.metrics {
+                    metrics.borrow_mut().record_warning(
+                        "pipelined has no effect together with canonical_write_order: reordered batch writes are \
+                         bound by name and issued one at a time to preserve the lock-acquisition order"
+                            .to_owned(),
+                    );
+                }
+            }
+
+            let mut exprs = exprs.into_iter().map(Some).collect::<Vec<_>>();
+            let bindings = order
+                .into_iter()
+                .map(|i| Binding::new(format!("batch_write_{i}"), exprs[i].take().expect("index used only once")))
+                .collect();
+            let results = (0..exprs.len())
+                .map(|i| Expression::Get {
+                    name: format!("batch_write_{i}"),
+                })
+                .collect();
+
+            Expression::Let {
+                bindings,
+                expr: Box::new(Expression::Seq {
+                    statements: results,
+                    pipelined: false,
+                }),
+            }
+        }
+        None => {
+            let pipelined = options.pipelined && exprs.len() > 1 && builder.supports_pipelining();
+            if pipelined {
+                if let Some(metrics) = &options.metrics {
+                    metrics.borrow_mut().record_capability("pipelining");
+                }
+            }
+            Expression::Seq {
+                pipelined,
+                statements: exprs,
+            }
+        }
+    };
+
+    Ok(super::expression::transform::hoist_transaction_constants(expr))
+}
+
+/// The order to issue a batch's write operations to the database in, to acquire row/table locks
+/// consistently across concurrent transactions touching the same tables — or `None` if any
+/// operation in the batch isn't a single, independent write (e.g. a read, or a nested write with
+/// children of its own), since only a pure write batch is safe to reorder wholesale: the JSON
+/// protocol batch contract already guarantees operations can't depend on each other's results (see
+/// [`translate_batch`]'s own doc comment), but a node with children has dependencies *within* its
+/// own graph whose relative order this function has no way to reason about.
+fn canonical_write_order(graphs: &[QueryGraph]) -> Option<Vec<usize>> {
+    if graphs.len() < 2 {
+        return None;
+    }
+
+    let mut keyed = graphs
+        .iter()
+        .enumerate()
+        .map(|(i, graph)| single_write_sort_key(graph).map(|key| (key, i)))
+        .collect::<Option<Vec<_>>>()?;
+
+    keyed.sort();
+    Some(keyed.into_iter().map(|(_, i)| i).collect())
+}
+
+/// The `(table, tiebreaker)` a batch operation sorts by for [`canonical_write_order`], if `graph`
+/// is a single root node with no children of its own that's a plain write query.
+fn single_write_sort_key(graph: &QueryGraph) -> Option<(String, String)> {
+    let root_nodes = graph.root_nodes();
+    let [root] = root_nodes.as_slice() else { return None };
+
+    if !graph.direct_child_pairs(root).is_empty() {
+        return None;
+    }
+
+    match graph.node_content(root)? {
+        Node::Query(Query::Write(write_query)) => {
+            Some((write_query.model().db_name().to_owned(), format!("{write_query:?}")))
+        }
+        _ => None,
+    }
 }
 
 struct NodeTranslator<'a, 'b> {
@@ -38,6 +491,7 @@ struct NodeTranslator<'a, 'b> {
     #[allow(dead_code)]
     parent_edges: &'b [EdgeRef],
     query_builder: &'b dyn QueryBuilder,
+    options: &'b CompileOptions,
 }
 
 impl<'a, 'b> NodeTranslator<'a, 'b> {
@@ -46,12 +500,14 @@ impl<'a, 'b> NodeTranslator<'a, 'b> {
         node: NodeRef,
         parent_edges: &'b [EdgeRef],
         query_builder: &'b dyn QueryBuilder,
+        options: &'b CompileOptions,
     ) -> Self {
         Self {
             graph,
             node,
             parent_edges,
             query_builder,
+            options,
         }
     }
 
@@ -64,7 +520,19 @@ impl<'a, 'b> NodeTranslator<'a, 'b> {
         match node {
             Node::Query(_) => self.translate_query(),
             // might be worth having Expression::Unit for this?
-            Node::Empty => Ok(Expression::Seq(vec![])),
+            Node::Empty => Ok(Expression::Seq {
+                statements: vec![],
+                pipelined: false,
+            }),
+            // `Node::Flow::If` is the query graph's "exists ? connect : create" branch, which
+            // `nested_connect_or_create` (query_graph_builder/write/nested/connect_or_create_nested.rs)
+            // wires up per entry for a to-many connectOrCreate — this compiler has no lowering for
+            // it, or for any other conditional branch, since `Expression` itself has no
+            // branch/conditional variant to lower one to. A to-many connectOrCreate with several
+            // entries would need one such branch compiled per entry, each threading the parent id
+            // the same way a plain nested create already does here; until `Expression` grows a
+            // branch construct, graphs containing one fall through to this arm instead of silently
+            // mistranslating.
             n => unimplemented!("{:?}", std::mem::discriminant(n)),
         }
     }
@@ -82,21 +550,45 @@ impl<'a, 'b> NodeTranslator<'a, 'b> {
         let mut node = self.graph.pluck_node(&self.node);
 
         for edge in self.parent_edges {
-            match self.graph.pluck_edge(edge) {
+            let dependency = self.graph.pluck_edge(edge);
+            if let Some(metrics) = &self.options.metrics {
+                metrics.borrow_mut().record_dependency(dependency_kind_name(&dependency));
+            }
+
+            match dependency {
                 QueryGraphDependency::ExecutionOrder => {}
                 QueryGraphDependency::ProjectedDataDependency(selection, f) => {
-                    let fields = selection
-                        .selections()
-                        .map(|field| {
-                            (
-                                field.clone(),
-                                PrismaValue::Placeholder {
-                                    name: generate_projected_dependency_name(self.graph.edge_source(edge), field),
-                                    r#type: PlaceholderType::Any,
-                                },
-                            )
-                        })
-                        .collect_vec();
+                    if let Some(field) = conflicting_literal_field(&node, selection.selections()) {
+                        return Err(TranslateError::ConflictingRelationInput {
+                            model: node_model_name(&node),
+                            field,
+                        });
+                    }
+
+                    let source = self.graph.edge_source(edge);
+                    let known_values = self
+                        .graph
+                        .node_content(&source)
+                        .and_then(|source_node| literal_values_for(source_node, selection.selections()));
+
+                    let fields = match known_values {
+                        // The source already carries a concrete value for every linked field (e.g. a
+                        // client-generated uuid default, or a literal the caller passed directly) —
+                        // use it as-is instead of making this node wait on the source's query result.
+                        Some(values) => selection.selections().cloned().zip(values).collect_vec(),
+                        None => selection
+                            .selections()
+                            .map(|field| {
+                                (
+                                    field.clone(),
+                                    PrismaValue::Placeholder {
+                                        name: generate_projected_dependency_name(source, field),
+                                        r#type: PlaceholderType::Any,
+                                    },
+                                )
+                            })
+                            .collect_vec(),
+                    };
 
                     // TODO: there are cases where we look at the number of results in some
                     // dependencies, these won't work with the current implementation and will
@@ -111,12 +603,18 @@ impl<'a, 'b> NodeTranslator<'a, 'b> {
         }
 
         let query: Query = node.try_into().expect("current node must be query");
-        let expr = translate_query(query, self.query_builder)?;
+        if let Some(metrics) = &self.options.metrics {
+            metrics.borrow_mut().record_query(query_kind_name(&query));
+        }
+        let expr = translate_query(query, self.query_builder, self.options)?;
 
         if !children.is_empty() {
             Ok(Expression::Let {
                 bindings: vec![Binding::new(self.node.id(), expr)],
-                expr: Box::new(Expression::Seq(children)),
+                expr: Box::new(Expression::Seq {
+                    statements: children,
+                    pipelined: false,
+                }),
             })
         } else {
             Ok(expr)
@@ -200,9 +698,34 @@ impl<'a, 'b> NodeTranslator<'a, 'b> {
     }
 
     fn process_child_with_dependencies(&mut self, node: NodeRef) -> TranslateResult<Expression> {
-        let bindings = self
-            .graph
-            .incoming_edges(&node)
+        let incoming = self.graph.incoming_edges(&node);
+
+        // `create_record` in the query graph builder leaves behind a create followed by a
+        // `find_unique` by the id it just wrote whenever the connector can't do `INSERT ...
+        // RETURNING` (or the create has a nested selection it can't serve from RETURNING
+        // alone). When that follow-up read asks for nothing beyond what the create's own
+        // result already carries, it can't observe anything new — reuse the create's result
+        // instead of issuing a second round trip for the same row.
+        if let [edge] = incoming.as_slice() {
+            let is_redundant = matches!(
+                self.graph.edge_content(edge),
+                Some(QueryGraphDependency::ProjectedDataDependency(..))
+            ) && self.graph.outgoing_edges(&node).is_empty()
+                && self
+                    .graph
+                    .node_content(&self.node)
+                    .zip(self.graph.node_content(&node))
+                    .is_some_and(|(parent, child)| is_redundant_follow_up_read(parent, child));
+
+            if is_redundant {
+                self.graph.mark_visited(&node);
+                self.graph.pluck_edge(edge);
+                self.graph.pluck_node(&node);
+                return Ok(Expression::Get { name: self.node.id() });
+            }
+        }
+
+        let bindings = incoming
             .into_iter()
             .flat_map(|edge| {
                 let Some(QueryGraphDependency::ProjectedDataDependency(selection, _)) = self.graph.edge_content(&edge)
@@ -226,7 +749,7 @@ impl<'a, 'b> NodeTranslator<'a, 'b> {
 
         // translate plucks the edges coming into node, we need to avoid accessing it afterwards
         let edges = self.graph.incoming_edges(&node);
-        let expr = NodeTranslator::new(self.graph, node, &edges, self.query_builder).translate()?;
+        let expr = NodeTranslator::new(self.graph, node, &edges, self.query_builder, self.options).translate()?;
 
         // we insert a MapField expression if the edge was a projected data dependency
         if !bindings.is_empty() {
@@ -240,6 +763,126 @@ impl<'a, 'b> NodeTranslator<'a, 'b> {
     }
 }
 
+/// The variant name of `dependency`, for
+/// [`CompileMetrics::dependency_kinds`](super::metrics::CompileMetrics::dependency_kinds).
+fn dependency_kind_name(dependency: &QueryGraphDependency) -> &'static str {
+    match dependency {
+        QueryGraphDependency::ExecutionOrder => "ExecutionOrder",
+        QueryGraphDependency::ProjectedDataDependency(..) => "ProjectedDataDependency",
+        QueryGraphDependency::DataDependency(..) => "DataDependency",
+        QueryGraphDependency::Then => "Then",
+        QueryGraphDependency::Else => "Else",
+    }
+}
+
+/// The variant name of `query`, for [`CompileMetrics::query_kinds`](super::metrics::CompileMetrics::query_kinds).
+fn query_kind_name(query: &Query) -> &'static str {
+    match query {
+        Query::Read(ReadQuery::RecordQuery(_)) => "RecordQuery",
+        Query::Read(ReadQuery::ManyRecordsQuery(_)) => "ManyRecordsQuery",
+        Query::Read(ReadQuery::RelatedRecordsQuery(_)) => "RelatedRecordsQuery",
+        Query::Read(ReadQuery::AggregateRecordsQuery(_)) => "AggregateRecordsQuery",
+        Query::Write(WriteQuery::CreateRecord(_)) => "CreateRecord",
+        Query::Write(WriteQuery::CreateManyRecords(_)) => "CreateManyRecords",
+        Query::Write(WriteQuery::UpdateRecord(_)) => "UpdateRecord",
+        Query::Write(WriteQuery::DeleteRecord(_)) => "DeleteRecord",
+        Query::Write(WriteQuery::UpdateManyRecords(_)) => "UpdateManyRecords",
+        Query::Write(WriteQuery::DeleteManyRecords(_)) => "DeleteManyRecords",
+        Query::Write(WriteQuery::ConnectRecords(_)) => "ConnectRecords",
+        Query::Write(WriteQuery::DisconnectRecords(_)) => "DisconnectRecords",
+        Query::Write(WriteQuery::ExecuteRaw(_)) => "ExecuteRaw",
+        Query::Write(WriteQuery::QueryRaw(_)) => "QueryRaw",
+        Query::Write(WriteQuery::Upsert(_)) => "Upsert",
+    }
+}
+
 fn generate_projected_dependency_name(source: NodeRef, field: &SelectedField) -> String {
     format!("{}${}", source.id(), field.prisma_name())
 }
+
+/// The write args of `node`, if it's a write node whose args can carry a
+/// literal value that a relation-connect dependency might also target (a
+/// scalar FK set directly alongside `connect`, for example).
+fn write_args_of(node: &Node) -> Option<&WriteArgs> {
+    match node {
+        Node::Query(Query::Write(WriteQuery::CreateRecord(cr))) => Some(&cr.args),
+        Node::Query(Query::Write(WriteQuery::UpdateRecord(UpdateRecord::WithSelection(ur)))) => Some(&ur.args),
+        Node::Query(Query::Write(WriteQuery::UpdateRecord(UpdateRecord::WithoutSelection(ur)))) => Some(&ur.args),
+        Node::Query(Query::Write(WriteQuery::UpdateManyRecords(umr))) => Some(&umr.args),
+        _ => None,
+    }
+}
+
+/// The field selection of a write that can stand in for a later read of the
+/// same row, i.e. what that write's own `RETURNING` produces.
+fn write_selected_fields(node: &Node) -> Option<&FieldSelection> {
+    match node {
+        Node::Query(Query::Write(WriteQuery::CreateRecord(cr))) => Some(&cr.selected_fields),
+        _ => None,
+    }
+}
+
+/// Whether `read`, reached from `write` through a single edge, is the
+/// follow-up `find_unique` the query graph builder adds after a create that
+/// can't rely on `INSERT ... RETURNING` (see `create_record` in
+/// `query_graph_builder::write::create`) — and whether that follow-up has
+/// nothing left to do: no filter of its own beyond the id the edge closure
+/// is about to substitute, no in-memory join for a nested selection, and no
+/// field the write doesn't already return.
+fn is_redundant_follow_up_read(write: &Node, read: &Node) -> bool {
+    let Some(written) = write_selected_fields(write) else {
+        return false;
+    };
+
+    let Node::Query(Query::Read(ReadQuery::RecordQuery(rq))) = read else {
+        return false;
+    };
+
+    rq.filter.is_none() && rq.nested.is_empty() && written.is_superset_of(&rq.selected_fields)
+}
+
+fn node_model_name(node: &Node) -> String {
+    match node {
+        Node::Query(Query::Write(wq)) => wq.model().name().to_owned(),
+        _ => String::new(),
+    }
+}
+
+/// Whether `node` already carries a concrete, user-supplied value for one of
+/// `fields` that a relation-connect dependency is about to overwrite. This is
+/// the compile-time signature of a client sending both a scalar FK directly
+/// and a `connect` for the relation it belongs to: the edge closure that's
+/// about to run would otherwise silently clobber the value the client set.
+fn conflicting_literal_field<'a>(node: &Node, fields: impl Iterator<Item = &'a SelectedField>) -> Option<String> {
+    let args = write_args_of(node)?;
+
+    fields.map(|field| field.db_name().into_owned()).find(|db_name| {
+        matches!(
+            args.get_field_value(db_name),
+            Some(query_structure::WriteOperation::Scalar(ScalarWriteOperation::Set(value)))
+                if !matches!(value, PrismaValue::Placeholder { .. })
+        )
+    })
+}
+
+/// If `node` is a write whose args already carry a concrete literal for every
+/// one of `fields` (e.g. a `@default(uuid())` resolved client-side before the
+/// graph was even built, or a value the caller passed directly), returns
+/// those values in the same order as `fields`. Returns `None` as soon as one
+/// field is missing, DB-generated, or itself a placeholder waiting on some
+/// other dependency — in which case the caller still needs to round-trip
+/// through `node`'s query result instead.
+fn literal_values_for<'a>(node: &Node, fields: impl Iterator<Item = &'a SelectedField>) -> Option<Vec<PrismaValue>> {
+    let args = write_args_of(node)?;
+
+    fields
+        .map(|field| match args.get_field_value(&field.db_name()) {
+            Some(query_structure::WriteOperation::Scalar(ScalarWriteOperation::Set(value)))
+                if !matches!(value, PrismaValue::Placeholder { .. }) =>
+            {
+                Some(value.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
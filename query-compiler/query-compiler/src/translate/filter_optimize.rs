@@ -0,0 +1,82 @@
+use query_structure::Filter;
+
+/// Factors conjuncts shared by every branch of an `OR` out in front of it, e.g. `(tenantId = x AND
+/// a = 1) OR (tenantId = x AND b = 2)` becomes `tenantId = x AND (a = 1 OR b = 2)`. The two are
+/// logically identical, but the hoisted form lets a query planner use an index on the common
+/// column instead of having to union-scan each branch separately.
+///
+/// Recurses into `And`/`Or`/`Not` so a shared conjunct is found regardless of how deep the `OR`
+/// sits in the filter tree; leaves every other filter kind untouched.
+pub(crate) fn optimize_filter(filter: Filter) -> Filter {
+    match filter {
+        Filter::And(parts) => Filter::And(parts.into_iter().map(optimize_filter).collect()),
+        Filter::Or(branches) => hoist_common_conjuncts(branches.into_iter().map(optimize_filter).collect()),
+        Filter::Not(parts) => Filter::Not(parts.into_iter().map(optimize_filter).collect()),
+        other => other,
+    }
+}
+
+/// The actual hoist: finds the conjuncts common to every branch and, if there are any, splits
+/// each branch into `(common conjuncts, remainder)` and rebuilds as `common AND (remainder_1 OR
+/// remainder_2 OR ...)`.
+///
+/// Bails out (returning the `OR` unchanged) rather than hoisting when:
+/// - there are fewer than two branches (nothing to factor across),
+/// - no conjunct is common to every branch, or
+/// - hoisting would leave some branch with no remainder, i.e. that branch's entire condition is
+///   already implied by the common conjuncts and the whole `OR` should really collapse to just
+///   the common filter. That's a different, stronger rewrite than "hoist a shared conjunct" and
+///   is out of scope here.
+fn hoist_common_conjuncts(branches: Vec<Filter>) -> Filter {
+    if branches.len() < 2 {
+        return Filter::Or(branches);
+    }
+
+    let conjunct_sets: Vec<Vec<Filter>> = branches.iter().map(conjuncts_of).collect();
+
+    let mut common = conjunct_sets[0].clone();
+    common.retain(|candidate| conjunct_sets[1..].iter().all(|set| set.contains(candidate)));
+
+    if common.is_empty() {
+        return Filter::Or(branches);
+    }
+
+    let remainders: Vec<Vec<Filter>> = conjunct_sets
+        .into_iter()
+        .map(|mut set| {
+            for shared in &common {
+                if let Some(pos) = set.iter().position(|f| f == shared) {
+                    set.remove(pos);
+                }
+            }
+            set
+        })
+        .collect();
+
+    if remainders.iter().any(Vec::is_empty) {
+        return Filter::Or(branches);
+    }
+
+    let or_branches = remainders
+        .into_iter()
+        .map(|mut remainder| {
+            if remainder.len() == 1 {
+                remainder.pop().unwrap()
+            } else {
+                Filter::And(remainder)
+            }
+        })
+        .collect();
+
+    common.push(Filter::Or(or_branches));
+    Filter::And(common)
+}
+
+/// The top-level conjuncts of `filter`: `And`'s own parts, or `filter` itself as the sole conjunct
+/// of a one-element "and".
+fn conjuncts_of(filter: &Filter) -> Vec<Filter> {
+    match filter {
+        Filter::And(parts) => parts.clone(),
+        other => vec![other.clone()],
+    }
+}
@@ -8,11 +8,15 @@ use write::translate_write_query;
 
 use crate::expression::Expression;
 
-use super::TranslateResult;
+use super::{CompileOptions, TranslateResult};
 
-pub(crate) fn translate_query(query: Query, builder: &dyn QueryBuilder) -> TranslateResult<Expression> {
+pub(crate) fn translate_query(
+    query: Query,
+    builder: &dyn QueryBuilder,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
     match query {
-        Query::Read(rq) => translate_read_query(rq, builder),
-        Query::Write(wq) => translate_write_query(wq, builder),
+        Query::Read(rq) => translate_read_query(rq, builder, options),
+        Query::Write(wq) => translate_write_query(wq, builder, options),
     }
 }
@@ -1,17 +1,21 @@
 use crate::{
     expression::{Binding, Expression, JoinExpression},
-    translate::TranslateResult,
+    translate::{filter_optimize::optimize_filter, CompileOptions, TranslateResult},
     TranslateError,
 };
 use itertools::Itertools;
 use query_builder::{QueryArgumentsExt, QueryBuilder, RelationLink};
-use query_core::{AggregateRecordsQuery, FilteredQuery, ReadQuery, RelatedRecordsQuery};
+use query_core::{AggregateRecordsQuery, FilteredQuery, QueryOption, ReadQuery, RelatedRecordsQuery};
 use query_structure::{
     ConditionValue, FieldSelection, Filter, PrismaValue, QueryArguments, QueryMode, RelationField, ScalarCondition,
     ScalarFilter, ScalarProjection,
 };
 
-pub(crate) fn translate_read_query(query: ReadQuery, builder: &dyn QueryBuilder) -> TranslateResult<Expression> {
+pub(crate) fn translate_read_query(
+    query: ReadQuery,
+    builder: &dyn QueryBuilder,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
     Ok(match query {
         ReadQuery::RecordQuery(rq) => {
             let selected_fields = rq.selected_fields.without_relations().into_virtuals_last();
@@ -21,27 +25,48 @@ pub(crate) fn translate_read_query(query: ReadQuery, builder: &dyn QueryBuilder)
                 rq.filter.expect("ReadOne query should always have filter set"),
             ))
             .with_take(Some(1));
+            let args = apply_soft_delete_filter(args, rq.model.name(), options);
+            let args = apply_filter_optimizations(args);
             let query = builder
-                .build_get_records(&rq.model, args, &selected_fields)
+                .build_get_records(&rq.model, args, &selected_fields, options.lock_mode, false)
                 .map_err(TranslateError::QueryBuildFailure)?;
 
             let expr = Expression::Query(query);
             let expr = Expression::Unique(Box::new(expr));
+            let expr = if rq.options.contains(QueryOption::ThrowOnEmpty) {
+                Expression::Required {
+                    expr: Box::new(expr),
+                    message: not_found_message(rq.model.name(), options),
+                }
+            } else {
+                expr
+            };
 
             if rq.nested.is_empty() {
                 expr
             } else {
-                add_inmemory_join(expr, rq.nested, builder)?
+                add_inmemory_join(expr, rq.nested, builder, options)?
             }
         }
 
         ReadQuery::ManyRecordsQuery(mrq) => {
             let selected_fields = mrq.selected_fields.without_relations().into_virtuals_last();
             let needs_reversed_order = mrq.args.needs_reversed_order();
+            let args = apply_max_rows_guard(mrq.args, options.max_rows);
+            let args = apply_max_limit_clamp(args, builder.max_limit(), options);
+            let args = apply_soft_delete_filter(args, mrq.model.name(), options);
+            let args = apply_filter_optimizations(args);
+            warn_on_missing_shard_key(&args, mrq.model.name(), options);
 
             // TODO: we ignore chunking for now
             let query = builder
-                .build_get_records(&mrq.model, mrq.args, &selected_fields)
+                .build_get_records(
+                    &mrq.model,
+                    args,
+                    &selected_fields,
+                    options.lock_mode,
+                    options.include_total_count,
+                )
                 .map_err(TranslateError::QueryBuildFailure)?;
 
             let expr = Expression::Query(query);
@@ -51,16 +76,24 @@ pub(crate) fn translate_read_query(query: ReadQuery, builder: &dyn QueryBuilder)
             } else {
                 expr
             };
+            let expr = if mrq.options.contains(QueryOption::ThrowOnEmpty) {
+                Expression::Required {
+                    expr: Box::new(expr),
+                    message: not_found_message(mrq.model.name(), options),
+                }
+            } else {
+                expr
+            };
 
             if mrq.nested.is_empty() {
                 expr
             } else {
-                add_inmemory_join(expr, mrq.nested, builder)?
+                add_inmemory_join(expr, mrq.nested, builder, options)?
             }
         }
 
         ReadQuery::RelatedRecordsQuery(rrq) => {
-            let (expr, _) = build_read_related_records(rrq, None, builder)?;
+            let (expr, _) = build_read_related_records(rrq, None, builder, options)?;
             expr
         }
 
@@ -83,10 +116,130 @@ pub(crate) fn translate_read_query(query: ReadQuery, builder: &dyn QueryBuilder)
     })
 }
 
+/// The message a failed [`Expression::Required`] carries for a `*OrThrow` operation on
+/// `model_name`: `options.not_found_message` with `{model}` substituted, or the engine's
+/// standard message if the caller didn't configure one.
+fn not_found_message(model_name: &str, options: &CompileOptions) -> String {
+    match &options.not_found_message {
+        Some(template) => template.replace("{model}", model_name),
+        None => "Expected a record, found none.".to_owned(),
+    }
+}
+
+/// ANDs `options.soft_delete_filters[model_name]` into `args`, if one is configured for that
+/// model. Called at every place this compiler reads records of a model — top-level and nested
+/// alike — so a soft-delete exclusion (or any other per-model filter a caller configures) can't be
+/// bypassed by reading the model through a relation instead of directly. Aggregate queries
+/// (`aggregate`, `groupBy`, `count`) are compiled without it.
+fn apply_soft_delete_filter(mut args: QueryArguments, model_name: &str, options: &CompileOptions) -> QueryArguments {
+    if let Some(filter) = options.soft_delete_filters.get(model_name) {
+        args.add_filter(filter.clone());
+    }
+    args
+}
+
+/// Rewrites `args`'s filter with [`optimize_filter`], hoisting conjuncts shared by every branch of
+/// an `OR` out in front of it. Applied at every place this compiler builds a filter to read records
+/// with, same as [`apply_soft_delete_filter`], so a caller benefits from the rewrite whether the
+/// `OR` came from a top-level `where` or from a nested relation read. Aggregate queries are
+/// compiled without it.
+fn apply_filter_optimizations(mut args: QueryArguments) -> QueryArguments {
+    args.filter = args.filter.map(optimize_filter);
+    args
+}
+
+/// Records a warning on `options.metrics` when `model_name` has a configured
+/// [`CompileOptions::shard_keys`] entry and `args`'s filter doesn't constrain that field anywhere,
+/// i.e. the database has no predicate on the partition key to prune partitions by and this read
+/// risks a full scan across every partition. A no-op when the model has no configured shard key,
+/// the filter already constrains it, or `options.metrics` isn't set.
+fn warn_on_missing_shard_key(args: &QueryArguments, model_name: &str, options: &CompileOptions) {
+    let Some(shard_key) = options.shard_keys.get(model_name) else {
+        return;
+    };
+    let Some(metrics) = &options.metrics else {
+        return;
+    };
+
+    let constrains_shard_key = args
+        .filter
+        .as_ref()
+        .is_some_and(|filter| filter_references_field(filter, shard_key));
+
+    if !constrains_shard_key {
+        metrics.borrow_mut().record_warning(format!(
+            "query on partitioned model `{model_name}` doesn't filter on its shard key `{shard_key}`; \
+             this scans every partition instead of pruning to the ones that matter"
+        ));
+    }
+}
+
+/// Whether `filter` constrains `field_name` anywhere in its tree, recursing into `And`/`Or`/`Not`.
+/// Used only to decide whether a shard key is constrained at all, not whether it's constrained in
+/// every branch of an `Or` (a looser check, but placing the predicate in just one `OR` arm would
+/// already get this warning out of the way for the easy, common case of a direct filter).
+fn filter_references_field(filter: &Filter, field_name: &str) -> bool {
+    match filter {
+        Filter::And(parts) | Filter::Or(parts) | Filter::Not(parts) => {
+            parts.iter().any(|f| filter_references_field(f, field_name))
+        }
+        Filter::Scalar(sf) => match &sf.projection {
+            ScalarProjection::Single(field) => field.name() == field_name,
+            ScalarProjection::Compound(fields) => fields.iter().any(|f| f.name() == field_name),
+        },
+        _ => false,
+    }
+}
+
+/// Caps an unbounded read at `max_rows + 1` rows. Leaves a read that already
+/// has its own `take` untouched, however large, since the guard only exists
+/// to catch queries that would otherwise stream every row in the table.
+fn apply_max_rows_guard(args: QueryArguments, max_rows: Option<usize>) -> QueryArguments {
+    match (args.take, max_rows) {
+        (None, Some(max_rows)) => args.with_take(Some(max_rows as i64 + 1)),
+        _ => args,
+    }
+}
+
+/// Clamps `take` to `max_limit` (the connector's cap on a single `LIMIT`, if any), preserving its
+/// sign since a negative `take` means "last N" rather than "first N". `skip` is untouched: the cap
+/// is on how many rows one `LIMIT` can ask for, not on how far an `OFFSET` can reach, so a `take`
+/// combined with a large `skip` is clamped the same way as one without. Records a warning on
+/// `options.metrics` when clamping actually changes the requested `take`.
+fn apply_max_limit_clamp(args: QueryArguments, max_limit: Option<i64>, options: &CompileOptions) -> QueryArguments {
+    match (args.take, max_limit) {
+        (Some(take), Some(max_limit)) if take.unsigned_abs() > max_limit.unsigned_abs() => {
+            let clamped = max_limit.abs() * take.signum();
+            if let Some(metrics) = &options.metrics {
+                metrics.borrow_mut().record_warning(format!(
+                    "take {take} on model `{}` exceeds the connector's max limit of {max_limit}; clamped to {clamped}",
+                    args.model.name()
+                ));
+            }
+
+            args.with_take(Some(clamped))
+        }
+        _ => args,
+    }
+}
+
+/// Every nested read — to-one or to-many, `relationJoins` preview feature enabled or not —
+/// compiles to a separate query stitched in afterwards by [`Expression::Join`]. This compiler has
+/// no native-SQL-join lowering to pick between: that strategy only exists in the legacy
+/// interpreter's `sql-query-connector` crate, gated by the same `relation_joins` feature this
+/// crate also enables on `sql-query-builder` (for [`QueryBuilder::build_get_related_records`],
+/// the m2m join-table query, which is unrelated). A consequence worth relying on rather than
+/// re-deriving: the sharp edges a native join strategy has to handle itself — deduplicating
+/// parent rows fanned out by the join, threading a window function through for stable nested
+/// ordering — don't apply here, since each relation is its own `LIMIT`/`ORDER BY`-bearing query.
+/// A to-one relation with no matching row naturally produces zero rows for [`Expression::Unique`]
+/// to collapse to `null`, the same as a required to-one would if its FK were somehow dangling;
+/// nullability isn't a case this function's caller needs to branch on.
 fn add_inmemory_join(
     parent: Expression,
     nested: Vec<ReadQuery>,
     builder: &dyn QueryBuilder,
+    options: &CompileOptions,
 ) -> TranslateResult<Expression> {
     let all_linking_fields = nested
         .iter()
@@ -132,7 +285,7 @@ fn add_inmemory_join(
                     }
                 })
                 .collect();
-            let (child, join_fields) = build_read_related_records(rrq, Some(conditions), builder)?;
+            let (child, join_fields) = build_read_related_records(rrq, Some(conditions), builder, options)?;
 
             Ok(JoinExpression {
                 child,
@@ -165,14 +318,15 @@ fn build_read_related_records(
     rrq: RelatedRecordsQuery,
     conditions: Option<Vec<ScalarCondition>>,
     builder: &dyn QueryBuilder,
+    options: &CompileOptions,
 ) -> TranslateResult<(Expression, JoinFields)> {
     let selected_fields = rrq.selected_fields.without_relations().into_virtuals_last();
     let needs_reversed_order = rrq.args.needs_reversed_order();
 
     let (mut child_query, join_on) = if rrq.parent_field.relation().is_many_to_many() {
-        build_read_m2m_query(rrq.parent_field, conditions, rrq.args, &selected_fields, builder)?
+        build_read_m2m_query(rrq.parent_field, conditions, rrq.args, &selected_fields, builder, options)?
     } else {
-        build_read_one2m_query(rrq.parent_field, conditions, rrq.args, &selected_fields, builder)?
+        build_read_one2m_query(rrq.parent_field, conditions, rrq.args, &selected_fields, builder, options)?
     };
 
     if needs_reversed_order {
@@ -180,7 +334,7 @@ fn build_read_related_records(
     }
 
     if !rrq.nested.is_empty() {
-        child_query = add_inmemory_join(child_query, rrq.nested, builder)?;
+        child_query = add_inmemory_join(child_query, rrq.nested, builder, options)?;
     };
     Ok((child_query, join_on))
 }
@@ -188,9 +342,10 @@ fn build_read_related_records(
 fn build_read_m2m_query(
     field: RelationField,
     conditions: Option<Vec<ScalarCondition>>,
-    args: QueryArguments,
+    mut args: QueryArguments,
     selected_fields: &FieldSelection,
     builder: &dyn QueryBuilder,
+    options: &CompileOptions,
 ) -> TranslateResult<(Expression, JoinFields)> {
     let condition = conditions.map(|mut conditions| {
         let condition = conditions
@@ -203,6 +358,9 @@ fn build_read_m2m_query(
         condition
     });
 
+    args = apply_soft_delete_filter(args, field.related_model().name(), options);
+    args = apply_filter_optimizations(args);
+
     let link = RelationLink::new(field, condition);
     let link_name = link.to_string();
 
@@ -219,6 +377,7 @@ fn build_read_one2m_query(
     mut args: QueryArguments,
     selected_fields: &FieldSelection,
     builder: &dyn QueryBuilder,
+    options: &CompileOptions,
 ) -> TranslateResult<(Expression, JoinFields)> {
     let related_scalars = field.related_field().left_scalars();
     let join_fields = related_scalars.iter().map(|sf| sf.name().to_owned()).collect();
@@ -239,10 +398,15 @@ fn build_read_one2m_query(
         }
     }
 
+    let args = apply_soft_delete_filter(args, field.related_model().name(), options);
+    let args = apply_filter_optimizations(args);
+
     let to_one_relation = !field.arity().is_list();
     let args = if to_one_relation { args.with_take(Some(1)) } else { args };
+    // Nested reads fetch related rows for an in-memory join, not the row a caller
+    // asked to lock; a lock requested on the top-level query doesn't propagate here.
     let query = builder
-        .build_get_records(&field.related_model(), args, selected_fields)
+        .build_get_records(&field.related_model(), args, selected_fields, None, false)
         .map_err(TranslateError::QueryBuildFailure)?;
 
     let mut expr = Expression::Query(query);
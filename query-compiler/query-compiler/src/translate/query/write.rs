@@ -1,19 +1,77 @@
 use query_builder::QueryBuilder;
 use query_core::{
-    ConnectRecords, DeleteManyRecords, DeleteRecord, DisconnectRecords, RawQuery, UpdateManyRecords, UpdateRecord,
-    UpdateRecordWithSelection, WriteQuery,
+    ConnectRecords, DeleteManyRecords, DeleteRecord, DeleteRecordFields, DisconnectRecords, RawQuery,
+    UpdateManyRecords, UpdateManyRecordsFields, UpdateRecord, UpdateRecordWithSelection, WriteQuery,
 };
-use query_structure::QueryArguments;
+use query_structure::{FieldSelection, Model, QueryArguments, SelectedField, WriteArgs};
 
-use crate::{expression::Expression, translate::TranslateResult, TranslateError};
+use crate::{expression::Expression, translate::TranslateResult, CompileOptions, TranslateError};
+
+/// The Prisma name of a field in `model` that `args` targets directly and that the database
+/// computes on its own (`@default(dbgenerated(..))`), if any. Such a field must never be written
+/// to explicitly: the value the caller supplied would just be overwritten by (or conflict with)
+/// the database's own computation, so it's rejected here instead of producing a query the
+/// database would reject or silently ignore.
+fn generated_column_write(model: &Model, args: &WriteArgs) -> Option<String> {
+    model.fields().scalar().find_map(|field| {
+        let targets_field = args.has_arg_for(field.db_name());
+        let is_generated = field.default_value().is_some_and(|default| default.is_dbgenerated());
+        (targets_field && is_generated).then(|| field.name().to_owned())
+    })
+}
+
+fn check_generated_column_write(model: &Model, args: &WriteArgs) -> TranslateResult<()> {
+    match generated_column_write(model, args) {
+        Some(field) => Err(TranslateError::WriteToGeneratedColumn { field }),
+        None => Ok(()),
+    }
+}
+
+/// Adds `model`'s configured [`CompileOptions::cdc_columns`] to `selected_fields`, for any that
+/// aren't already selected, so the write's `RETURNING` carries what a change-data-capture
+/// subscriber needs to publish the event alongside whatever the caller actually asked for. Mirrors
+/// how [`query_builder::TOTAL_COUNT_ALIAS`] adds an extra, unasked-for column to a read and leaves
+/// a response-shaping layer above this crate to pull it out of the raw row (by the known field
+/// names in `options.cdc_columns`) before handing the record back to the client — this compiler's
+/// `Expression` has no construct of its own for splitting one query's result into a client-visible
+/// part and a side channel, so widening the selection is as far as it can go towards the request
+/// on its own.
+fn add_cdc_columns(model: &Model, selected_fields: FieldSelection, options: &CompileOptions) -> FieldSelection {
+    let Some(cdc_fields) = options.cdc_columns.get(model.name()) else {
+        return selected_fields;
+    };
+
+    let mut selections = selected_fields.into_inner();
+    for field_name in cdc_fields {
+        if selections.iter().any(|f| f.prisma_name() == field_name.as_str()) {
+            continue;
+        }
+        if let Some(field) = model.fields().scalar().find(|f| f.name() == field_name) {
+            selections.push(SelectedField::Scalar(field));
+        }
+    }
+    FieldSelection::new(selections)
+}
+
+pub(crate) fn translate_write_query(
+    query: WriteQuery,
+    builder: &dyn QueryBuilder,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
+    if !matches!(query, WriteQuery::QueryRaw(_) | WriteQuery::ExecuteRaw(_)) && query.model().is_view() {
+        return Err(TranslateError::WriteToView(query.model().name().to_owned()));
+    }
 
-pub(crate) fn translate_write_query(query: WriteQuery, builder: &dyn QueryBuilder) -> TranslateResult<Expression> {
     Ok(match query {
         WriteQuery::CreateRecord(cr) => {
+            check_generated_column_write(&cr.model, &cr.args)?;
+
+            let selected_fields = add_cdc_columns(&cr.model, cr.selected_fields, options);
+
             // TODO: MySQL needs additional logic to generate IDs on our side.
             // See sql_query_connector::database::operations::write::create_record
             let query = builder
-                .build_create_record(&cr.model, cr.args, &cr.selected_fields)
+                .build_create_record(&cr.model, cr.args, &selected_fields)
                 .map_err(TranslateError::QueryBuildFailure)?;
 
             // TODO: we probably need some additional node type or extra info in the WriteQuery node
@@ -23,19 +81,38 @@ pub(crate) fn translate_write_query(query: WriteQuery, builder: &dyn QueryBuilde
         }
 
         WriteQuery::CreateManyRecords(cmr) => {
-            if let Some(selected_fields) = cmr.selected_fields {
-                Expression::Concat(
-                    builder
-                        .build_inserts(&cmr.model, cmr.args, cmr.skip_duplicates, Some(&selected_fields.fields))
-                        .map_err(TranslateError::QueryBuildFailure)?
-                        .into_iter()
-                        .map(Expression::Query)
-                        .collect::<Vec<_>>(),
-                )
+            for args in &cmr.args {
+                check_generated_column_write(&cmr.model, args)?;
+            }
+
+            let skip_duplicates = cmr.skip_duplicates || options.idempotent;
+            if let Some(selected_fields) = cmr.selected_fields.map(|sf| add_cdc_columns(&cmr.model, sf, options)) {
+                if options.preserve_insertion_order && !skip_duplicates && cmr.args.len() > 1 {
+                    Expression::Concat(
+                        cmr.args
+                            .into_iter()
+                            .map(|args| {
+                                builder
+                                    .build_create_record(&cmr.model, args, &selected_fields)
+                                    .map(Expression::Query)
+                                    .map_err(TranslateError::QueryBuildFailure)
+                            })
+                            .collect::<TranslateResult<Vec<_>>>()?,
+                    )
+                } else {
+                    Expression::Concat(
+                        builder
+                            .build_inserts(&cmr.model, cmr.args, skip_duplicates, Some(&selected_fields.fields))
+                            .map_err(TranslateError::QueryBuildFailure)?
+                            .into_iter()
+                            .map(Expression::Query)
+                            .collect::<Vec<_>>(),
+                    )
+                }
             } else {
                 Expression::Sum(
                     builder
-                        .build_inserts(&cmr.model, cmr.args, cmr.skip_duplicates, None)
+                        .build_inserts(&cmr.model, cmr.args, skip_duplicates, None)
                         .map_err(TranslateError::QueryBuildFailure)?
                         .into_iter()
                         .map(Expression::Execute)
@@ -52,6 +129,12 @@ pub(crate) fn translate_write_query(query: WriteQuery, builder: &dyn QueryBuilde
             limit,
             ..
         }) => {
+            check_generated_column_write(&model, &args)?;
+
+            let selected_fields = selected_fields.map(|sf| UpdateManyRecordsFields {
+                fields: add_cdc_columns(&model, sf.fields, options),
+                ..sf
+            });
             let projection = selected_fields.as_ref().map(|f| &f.fields);
             let updates = if record_filter.has_selectors() {
                 // we'll need to implement the equivalent of:
@@ -91,11 +174,14 @@ pub(crate) fn translate_write_query(query: WriteQuery, builder: &dyn QueryBuilde
             // TODO: we're ignoring selection order
             selection_order: _,
         })) => {
+            check_generated_column_write(&model, &args)?;
+
+            let selected_fields = add_cdc_columns(&model, selected_fields, options);
             let query = if args.is_empty() {
                 // if there's no args we can just issue a read query
                 let args = QueryArguments::from((model.clone(), record_filter.filter)).with_take(Some(1));
                 builder
-                    .build_get_records(&model, args, &selected_fields)
+                    .build_get_records(&model, args, &selected_fields, None, false)
                     .map_err(TranslateError::QueryBuildFailure)?
             } else {
                 builder
@@ -106,13 +192,17 @@ pub(crate) fn translate_write_query(query: WriteQuery, builder: &dyn QueryBuilde
         }
 
         WriteQuery::Upsert(upsert) => {
+            check_generated_column_write(upsert.model(), upsert.create())?;
+            check_generated_column_write(upsert.model(), upsert.update())?;
+
+            let selected_fields = add_cdc_columns(upsert.model(), upsert.selected_fields().clone(), options);
             let query = builder
                 .build_upsert(
                     upsert.model(),
                     upsert.filter().clone(),
                     upsert.create().clone(),
                     upsert.update().clone(),
-                    upsert.selected_fields(),
+                    &selected_fields,
                     &upsert.unique_constraints(),
                 )
                 .map_err(TranslateError::QueryBuildFailure)?;
@@ -145,6 +235,10 @@ pub(crate) fn translate_write_query(query: WriteQuery, builder: &dyn QueryBuilde
             record_filter,
             selected_fields,
         }) => {
+            let selected_fields = selected_fields.map(|sf| DeleteRecordFields {
+                fields: add_cdc_columns(&model, sf.fields, options),
+                ..sf
+            });
             let selected_fields = selected_fields.as_ref().map(|sf| &sf.fields);
             let query = builder
                 .build_delete(&model, record_filter, selected_fields)
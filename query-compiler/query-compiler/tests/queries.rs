@@ -1,24 +1,248 @@
-use std::{fs, sync::Arc};
+use std::{collections::HashMap, fs, sync::Arc};
 
 use quaint::{
     prelude::{ConnectionInfo, ExternalConnectionInfo, SqlFamily},
-    visitor::Postgres,
+    visitor::{Mssql, Mysql, Postgres, Sqlite},
+};
+use telemetry::TraceParent;
+use query_compiler::{translate::TranslateResult, CompileOptions, Expression, LockMode, TranslateError};
+use query_core::{schema::QuerySchema, QueryDocument, QueryGraph, QueryGraphBuilder};
+use query_structure::{
+    psl, ConditionValue, Filter, PrismaValue, QueryMode, ScalarCondition, ScalarFilter, ScalarProjection,
 };
-use query_core::{QueryDocument, QueryGraphBuilder};
-use query_structure::psl;
 use request_handlers::{JsonBody, JsonSingleQuery, RequestBody};
 use sql_query_builder::{Context, SqlQueryBuilder};
+use tracing_test::{logs_contain, traced_test};
+
+fn query_schema() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/schema.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema with `relationMode = "prisma"`, i.e. one
+/// where referential actions are emulated by the `QueryGraphBuilder` instead of enforced by the
+/// database's own foreign keys.
+fn query_schema_with_relation_mode_prisma() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-relation-mode-prisma.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema with a `String[]` field, i.e. one that
+/// exercises scalar-list filters (`has`/`hasEvery`/`hasSome`).
+fn query_schema_with_scalar_lists() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-scalar-lists.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema with a self-relation (`Category.parent` /
+/// `Category.children`), i.e. one that exercises multi-hop relation filters on the same table.
+fn query_schema_with_self_relation() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-self-relation.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema where the primary key is a client-generated
+/// `@default(uuid())` rather than an autoincrement, i.e. one that exercises a nested create whose
+/// linking FK is already known at compile time instead of coming back from the database.
+fn query_schema_with_uuid_pk() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-uuid-pk.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema with a `Json?` field, i.e. one that
+/// exercises ordering by a value extracted from a JSON field at a path.
+fn query_schema_with_json_field() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-json-field.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema where a field (`Invoice.total`) is
+/// `@default(dbgenerated(..))`, i.e. one that exercises rejecting writes targeting a
+/// database-computed column.
+fn query_schema_with_generated_column() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-generated-column.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema where the model and a field are
+/// `@@map`/`@map`ped to different database names, i.e. one that exercises SQL generation using DB
+/// names while the Prisma names stay untouched in the selection passed down to the builder.
+fn query_schema_with_mapped_names() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-mapped-names.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema`], but built from a schema with a nullable to-one relation
+/// (`Post.editor`), i.e. one that exercises a nested include whose related row may not exist.
+fn query_schema_with_nullable_to_one() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-nullable-to-one.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+fn build_graph(query_schema: &Arc<QuerySchema>, query: JsonSingleQuery) -> QueryGraph {
+    let request = RequestBody::Json(JsonBody::Single(query));
+    let doc = request.into_doc(query_schema).unwrap();
+
+    let QueryDocument::Single(query) = doc else {
+        panic!("expected single query");
+    };
+
+    let (graph, _serializer) = QueryGraphBuilder::new(query_schema).build(query).unwrap();
+    graph
+}
+
+/// Builds and translates the query in `json` against `query_schema`, as if it
+/// had arrived over the JSON protocol targeting Postgres.
+fn translate_fixture(
+    query_schema: &Arc<QuerySchema>,
+    json: &str,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let query: JsonSingleQuery = serde_json::from_str(json).unwrap();
+    let graph = build_graph(query_schema, query);
+
+    query_compiler::translate(graph, &builder, options)
+}
+
+/// Same as [`translate_fixture`], but compiles against SQLite instead of Postgres. Used to pin
+/// down `LIKE` escaping behavior, which (unlike Postgres/MySQL) SQLite doesn't treat `\` as the
+/// default escape character for, so we can't rely on Postgres-only coverage there.
+fn translate_fixture_sqlite(query_schema: &Arc<QuerySchema>, json: &str, options: &CompileOptions) -> TranslateResult<Expression> {
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(SqlFamily::Sqlite, "main".to_owned(), None));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Sqlite<'_>>::new(ctx);
+
+    let query: JsonSingleQuery = serde_json::from_str(json).unwrap();
+    let graph = build_graph(query_schema, query);
+
+    query_compiler::translate(graph, &builder, options)
+}
+
+/// Same as [`translate_fixture`], but compiles against MSSQL instead of Postgres. See
+/// [`translate_fixture_sqlite`] for why this matters for `LIKE` escaping specifically.
+fn translate_fixture_mssql(query_schema: &Arc<QuerySchema>, json: &str, options: &CompileOptions) -> TranslateResult<Expression> {
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(SqlFamily::Mssql, "dbo".to_owned(), None));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Mssql<'_>>::new(ctx);
+
+    let query: JsonSingleQuery = serde_json::from_str(json).unwrap();
+    let graph = build_graph(query_schema, query);
+
+    query_compiler::translate(graph, &builder, options)
+}
+
+/// The result of compiling one fixture's plan under two different `CompileOptions`, for
+/// regression-testing a compiler change (or an option toggle) across a corpus of fixtures without
+/// hand-diffing snapshots one by one.
+struct PlanDiff {
+    fixture: &'static str,
+    before: String,
+    after: String,
+}
+
+impl PlanDiff {
+    fn is_changed(&self) -> bool {
+        self.before != self.after
+    }
+
+    /// Renders the plan diff the same way a failed snapshot assertion does elsewhere in the
+    /// workspace: insertions/deletions highlighted via `dissimilar`.
+    fn render(&self) -> String {
+        dissimilar::diff(&self.before, &self.after)
+            .into_iter()
+            .map(|chunk| match chunk {
+                dissimilar::Chunk::Equal(text) => text.to_string(),
+                dissimilar::Chunk::Delete(text) => format!("\x1b[41m{text}\x1b[0m"),
+                dissimilar::Chunk::Insert(text) => format!("\x1b[42m{text}\x1b[0m"),
+            })
+            .collect()
+    }
+}
+
+fn diff_plans(
+    query_schema: &Arc<QuerySchema>,
+    fixtures: &[(&'static str, &str)],
+    before: &CompileOptions,
+    after: &CompileOptions,
+) -> Vec<PlanDiff> {
+    fixtures
+        .iter()
+        .map(|(fixture, json)| PlanDiff {
+            fixture,
+            before: translate_fixture(query_schema, json, before)
+                .unwrap()
+                .pretty_print(false, 80)
+                .unwrap(),
+            after: translate_fixture(query_schema, json, after)
+                .unwrap()
+                .pretty_print(false, 80)
+                .unwrap(),
+        })
+        .collect()
+}
+
+fn summarize_plan_diffs(diffs: &[PlanDiff]) -> String {
+    let changed = diffs.iter().filter(|d| d.is_changed()).count();
+    format!("{} plans unchanged, {} changed", diffs.len() - changed, changed)
+}
 
 #[test]
 fn queries() {
     insta::glob!("data/*.json", |path| {
-        let schema_string = include_str!("data/schema.prisma");
-        let schema = psl::validate(schema_string.into());
-
-        assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
-
-        let schema = Arc::new(schema);
-        let query_schema = Arc::new(query_core::schema::build(schema, true));
+        let query_schema = query_schema();
 
         let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
             SqlFamily::Postgres,
@@ -28,21 +252,2195 @@ fn queries() {
 
         let query = fs::read_to_string(path).unwrap();
         let query: JsonSingleQuery = serde_json::from_str(&query).unwrap();
-
-        let request = RequestBody::Json(JsonBody::Single(query));
-        let doc = request.into_doc(&query_schema).unwrap();
-
-        let QueryDocument::Single(query) = doc else {
-            panic!("expected single query");
-        };
-
-        let (graph, _serializer) = QueryGraphBuilder::new(&query_schema).build(query).unwrap();
+        let graph = build_graph(&query_schema, query);
 
         let ctx = Context::new(&connection_info, None);
         let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
 
-        let expr = query_compiler::translate(graph, &builder).unwrap();
+        let expr = query_compiler::translate(graph, &builder, &Default::default()).unwrap();
         let pretty = expr.pretty_print(false, 80).unwrap();
         insta::assert_snapshot!(pretty);
     });
 }
+
+#[test]
+fn translate_subgraph_compiles_an_isolated_branch() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let query: JsonSingleQuery = serde_json::from_str(include_str!("data/create-nested-create.json")).unwrap();
+    let graph = build_graph(&query_schema, query);
+
+    let root = graph.root_nodes().into_iter().next().unwrap();
+    let (_, child) = graph
+        .direct_child_pairs(&root)
+        .into_iter()
+        .next()
+        .expect("nested create should have at least one child node");
+
+    let expr = query_compiler::translate_subgraph(graph, &child.id(), &builder, &Default::default()).unwrap();
+    assert!(!expr.pretty_print(false, 80).unwrap().is_empty());
+}
+
+#[test]
+fn bind_parameters_lists_placeholders_referenced_across_the_plan() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(&query_schema, include_str!("data/query-one2m.json"), &Default::default()).unwrap();
+
+    let params = expr.bind_parameters();
+    assert!(params.iter().any(|p| p.name == "@parent$userId"));
+}
+
+#[test]
+fn metrics_option_reports_dependency_kinds_for_a_nested_delete() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let query_schema = query_schema();
+    let metrics = Rc::new(RefCell::new(query_compiler::CompileMetrics::default()));
+    let options = CompileOptions {
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+
+    translate_fixture(&query_schema, include_str!("data/cases/update-nested-delete.json"), &options).unwrap();
+
+    let metrics = metrics.borrow();
+    assert!(metrics.dependency_kinds.contains_key("ProjectedDataDependency"));
+    assert!(metrics.query_kinds.contains_key("DeleteManyRecords"));
+}
+
+#[test]
+fn create_targeting_a_generated_column_is_rejected() {
+    let query_schema = query_schema_with_generated_column();
+    let err = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/create-generated-column-write.json"),
+        &Default::default(),
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, TranslateError::WriteToGeneratedColumn { field } if field == "total"));
+}
+
+#[test]
+fn create_omitting_a_generated_column_succeeds() {
+    let query_schema = query_schema_with_generated_column();
+    assert!(translate_fixture(
+        &query_schema,
+        include_str!("data/cases/create-generated-column-omitted.json"),
+        &Default::default(),
+    )
+    .is_ok());
+}
+
+#[test]
+fn mapped_model_and_field_names_are_used_in_sql_not_the_output_shape() {
+    let query_schema = query_schema_with_mapped_names();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/update-mapped-names.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.contains("customers") && pretty.contains("full_name"),
+        "expected the SQL to target the @@map/@map'd DB names: {pretty}"
+    );
+    assert!(
+        !pretty.contains("\"Customer\"") && !pretty.to_lowercase().contains("fullname ="),
+        "expected the SQL to use DB names rather than the Prisma model/field names: {pretty}"
+    );
+}
+
+#[test]
+fn idempotent_option_forces_skip_duplicates_on_create_many() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        idempotent: true,
+        ..Default::default()
+    };
+    let fixture = include_str!("data/cases/create-many-differing-fields.json");
+    assert!(translate_fixture(&query_schema, fixture, &options).is_ok());
+}
+
+#[test]
+fn create_many_with_differing_row_shapes_aligns_every_row_to_the_unioned_columns() {
+    // The two rows in the fixture don't share the same fields: only the first omits
+    // `publishedAt`. All rows still need to land in a single multi-row INSERT, so the missing
+    // field must be filled in for that row rather than shifting every later column over.
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/create-many-differing-fields.json");
+    let pretty = translate_fixture(&query_schema, fixture, &Default::default())
+        .unwrap()
+        .pretty_print(false, 200)
+        .unwrap();
+
+    let insert_pos = pretty.find("INSERT INTO").expect("expected an INSERT statement: {pretty}");
+    let insert = &pretty[insert_pos..];
+    let columns_start = insert.find('(').unwrap() + 1;
+    let columns_end = insert.find(')').unwrap();
+    let columns = &insert[columns_start..columns_end];
+    assert_eq!(
+        columns, "\"title\",\"userId\",\"publishedAt\"",
+        "expected the row missing publishedAt to still contribute to the unioned column set: {insert}"
+    );
+
+    let values_start = insert.find("VALUES").expect("expected a VALUES clause: {insert}") + "VALUES".len();
+    let rows: Vec<&str> = insert[values_start..]
+        .trim_start()
+        .split("),(")
+        .map(|row| row.trim_start_matches('(').trim_end_matches(')'))
+        .collect();
+    assert_eq!(rows.len(), 2, "expected one row per createMany argument: {insert}");
+
+    for row in &rows {
+        assert_eq!(
+            row.split(',').count(),
+            3,
+            "expected every row's values to line up with the 3 unioned columns: {insert}"
+        );
+    }
+
+    let published_at_column = 2;
+    assert_eq!(
+        rows[0].split(',').nth(published_at_column).unwrap(),
+        "NULL",
+        "expected the row that omitted publishedAt to fill it in as NULL rather than shift columns: {insert}"
+    );
+    assert_ne!(
+        rows[1].split(',').nth(published_at_column).unwrap(),
+        "NULL",
+        "expected the row that provided publishedAt to keep its own value: {insert}"
+    );
+}
+
+#[test]
+fn one_to_many_disconnect_by_id_finds_children_before_nulling_their_fk() {
+    // `QueryGraphBuilder` already scopes a by-id disconnect to the parent: it first reads the
+    // children matching both the parent relation and the given ids (so a child id that isn't
+    // actually the parent's simply isn't found, rather than being disconnected or erroring), then
+    // nulls the FK only on the ids that came back. This pins that two-step shape down for the
+    // inlined-on-child case (`User.editedPosts`, a nullable to-one on `Post`).
+    let query_schema = query_schema_with_nullable_to_one();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/update-one2m-disconnect-by-id.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("SELECT") && pretty.to_uppercase().contains("UPDATE"),
+        "expected a find-children read followed by an update nulling the FK: {pretty}"
+    );
+    assert!(
+        pretty.matches("editorId").count() >= 2,
+        "expected editorId to scope the find and be nulled in the update: {pretty}"
+    );
+}
+
+#[test]
+fn preserve_insertion_order_compiles_create_many_and_return_as_one_insert_per_row() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        preserve_insertion_order: true,
+        ..Default::default()
+    };
+    let expr =
+        translate_fixture(&query_schema, include_str!("data/cases/create-many-and-return.json"), &options).unwrap();
+
+    let Expression::Seq { statements, .. } = expr else {
+        panic!("expected a Seq");
+    };
+    let Expression::Concat(inserts) = &statements[0] else {
+        panic!("expected a Concat of inserts");
+    };
+
+    assert_eq!(inserts.len(), 3, "expected one insert statement per row to preserve insertion order");
+    assert!(inserts.iter().all(|e| matches!(e, Expression::Query(_))));
+}
+
+#[test]
+fn preserve_insertion_order_is_a_no_op_when_not_requested() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/create-many-and-return.json"),
+        &Default::default(),
+    )
+    .unwrap();
+
+    let Expression::Seq { statements, .. } = expr else {
+        panic!("expected a Seq");
+    };
+    let Expression::Concat(inserts) = &statements[0] else {
+        panic!("expected a Concat of inserts");
+    };
+
+    assert_eq!(inserts.len(), 1, "expected all 3 rows batched into a single multi-row insert as before");
+}
+
+#[test]
+fn find_unique_or_throw_wraps_the_result_in_required_with_the_default_message() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-unique-or-throw.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.contains("required") && pretty.contains("Expected a record, found none."),
+        "expected the default not-found message when none is configured: {pretty}"
+    );
+}
+
+#[test]
+fn find_unique_or_throw_uses_a_configured_message_template() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        not_found_message: Some("No {model} found for you".to_owned()),
+        ..Default::default()
+    };
+    let expr =
+        translate_fixture(&query_schema, include_str!("data/cases/find-unique-or-throw.json"), &options).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.contains("No User found for you"),
+        "expected the configured template with {{model}} interpolated: {pretty}"
+    );
+}
+
+#[test]
+fn find_first_or_throw_wraps_the_result_in_required_too() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        not_found_message: Some("No {model} matched".to_owned()),
+        ..Default::default()
+    };
+    let expr =
+        translate_fixture(&query_schema, include_str!("data/cases/find-first-or-throw.json"), &options).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.contains("No User matched"),
+        "expected findFirstOrThrow's ManyRecordsQuery plan to also be wrapped in required: {pretty}"
+    );
+}
+
+#[test]
+fn find_unique_without_or_throw_is_not_wrapped_in_required() {
+    let query_schema = query_schema();
+    let expr =
+        translate_fixture(&query_schema, include_str!("data/cases/find-unique-simple.json"), &Default::default())
+            .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(!pretty.contains("required"), "plain findUnique shouldn't throw on empty: {pretty}");
+}
+
+#[test]
+fn aggregate_mixing_count_sum_and_avg_compiles_to_a_single_query() {
+    // `translate_query` hands the whole `AggregationSelection` list to one `build_aggregate` call,
+    // which folds every selection (count, sum, avg, ...) into the same `SELECT`, each under its own
+    // aliased column (`_count._all`, `_sum.price`, `_avg.price`). Reassembling those flat columns
+    // into the nested `{ _count, _sum: { price }, _avg: { price } }` response shape is a response-IR
+    // concern downstream of this crate, which only ever returns flat rows — there's no flattening
+    // bug to reproduce here, just the single-query shape to pin down.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/aggregate-count-sum-avg.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert_eq!(expr.estimated_cost(), 1, "expected a single query, not one per aggregate kind");
+    assert!(
+        pretty.contains("_count._all") && pretty.contains("_sum.price") && pretty.contains("_avg.price"),
+        "expected all three aggregate kinds as columns of the one query: {pretty}"
+    );
+}
+
+#[test]
+fn decimal_filter_value_serializes_deterministically_and_without_losing_digits() {
+    // `PrismaValue::Float` (used for both `Float` and `Decimal` fields) serializes through `f64`
+    // rather than as a string, since the JSON protocol here represents numbers as `f64` and this
+    // workspace doesn't enable serde_json's `arbitrary_precision`. Within that bound, the
+    // workspace-wide `float_roundtrip` feature guarantees serde_json always emits the shortest
+    // string that parses back to the same `f64` bits, so an ordinary-precision decimal like
+    // `19.99` round-trips byte-for-byte and compiling the same plan twice produces identical JSON.
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/decimal-range-filter.json");
+
+    let expr_1 = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let expr_2 = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+
+    let json_1 = serde_json::to_string(&expr_1).unwrap();
+    let json_2 = serde_json::to_string(&expr_2).unwrap();
+
+    assert_eq!(json_1, json_2, "compiling the same plan twice should serialize identically");
+    assert!(json_1.contains("19.99"), "expected the decimal filter bound to its exact value: {json_1}");
+    assert!(json_1.contains("99.99"), "expected the decimal filter bound to its exact value: {json_1}");
+}
+
+#[test]
+fn explicit_casts_annotates_a_cross_statement_placeholder_with_its_type() {
+    // The nested post `create` doesn't know its parent's id until the user insert comes back, so
+    // its `userId` FK compiles to a symbolic placeholder rather than a literal — exactly the kind
+    // of parameter a driver adapter can't infer a type for on its own.
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        explicit_casts: true,
+        ..Default::default()
+    };
+    let expr = translate_fixture(&query_schema, include_str!("data/create-nested-create.json"), &options).unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+
+    assert!(
+        pretty.to_lowercase().contains("userid") && pretty.contains("::int4"),
+        "expected the nested create's parent-id placeholder to be cast to int4: {pretty}"
+    );
+}
+
+#[test]
+fn explicit_casts_is_a_no_op_when_not_requested() {
+    let query_schema = query_schema();
+    let expr =
+        translate_fixture(&query_schema, include_str!("data/create-nested-create.json"), &Default::default())
+            .unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+
+    assert!(!pretty.contains("::int4"), "expected bare parameters without explicit_casts: {pretty}");
+}
+
+#[test]
+fn plan_exceeding_max_cost_is_rejected() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        max_cost: Some(0),
+        ..Default::default()
+    };
+    let err = translate_fixture(&query_schema, include_str!("data/query-one2m.json"), &options).unwrap_err();
+    assert!(matches!(err, TranslateError::CostLimitExceeded { .. }));
+}
+
+#[test]
+fn max_rows_guard_limits_an_unbounded_find_many() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        max_rows: Some(5),
+        ..Default::default()
+    };
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-empty-where.json"),
+        &options,
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("LIMIT"),
+        "expected a LIMIT clause on the otherwise-unbounded read: {pretty}"
+    );
+    assert!(
+        pretty.contains("BigInt(6)"),
+        "expected the guard to cap the read at max_rows + 1: {pretty}"
+    );
+}
+
+#[test]
+fn max_rows_guard_leaves_an_explicit_take_untouched() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        max_rows: Some(5),
+        ..Default::default()
+    };
+    let expr = translate_fixture(&query_schema, include_str!("data/cases/find-many-take-5.json"), &options).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.contains("BigInt(5)"),
+        "expected the user's own take to be left alone: {pretty}"
+    );
+    assert!(
+        !pretty.contains("BigInt(6)"),
+        "did not expect the guard to change an explicit take: {pretty}"
+    );
+}
+
+#[test]
+fn range_filter_on_one_field_binds_two_distinct_params() {
+    // `where: { id: { gt: 18, lt: 65 } }` is two predicates on the same column. Each condition's
+    // bound value is pushed onto the compiled query's own `params` independently (there's no
+    // named-placeholder reuse in play here: ordinary filter literals aren't `PrismaValue::Placeholder`,
+    // they're plain values appended to `DbQuery::params` one at a time), so there's no way for the
+    // two to collide into a single shared binding the way a named placeholder reused across two
+    // different values would.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-range-filter-same-field.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        pretty.contains("Int(18)") && pretty.contains("Int(65)"),
+        "expected both range bounds to appear as distinct bound params: {pretty}"
+    );
+}
+
+#[test]
+fn soft_delete_filter_applies_to_a_nested_read_of_that_model_too() {
+    // A `soft_delete_filters["Post"]` exclusion must follow `Post` wherever it's read, not just
+    // from a top-level `findMany(Post)` — otherwise `findMany(User, { include: { posts } })`
+    // would leak soft-deleted posts back in through the relation.
+    let query_schema = query_schema();
+    let post_model = query_schema.internal_data_model.find_model("Post").unwrap();
+    let published_at = post_model.fields().find_from_scalar("publishedAt").unwrap();
+
+    let mut soft_delete_filters = HashMap::new();
+    soft_delete_filters.insert(
+        "Post".to_owned(),
+        Filter::Scalar(ScalarFilter {
+            condition: ScalarCondition::NotEquals(ConditionValue::Value(PrismaValue::Null)),
+            projection: ScalarProjection::Single(published_at),
+            mode: QueryMode::Default,
+        }),
+    );
+    let options = CompileOptions {
+        soft_delete_filters,
+        ..Default::default()
+    };
+
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-users-include-posts.json"),
+        &options,
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    let occurrences = pretty.matches("IS NOT NULL").count();
+    assert_eq!(
+        occurrences, 1,
+        "expected the soft-delete exclusion to apply once, to the nested posts read: {pretty}"
+    );
+}
+
+#[test]
+fn common_conjunct_across_or_branches_is_factored_out() {
+    // `where: { OR: [{ userId: 1, title: "Hello" }, { userId: 1, publishedAt: null }] }` has
+    // `userId = 1` in both branches, so it should be hoisted in front of the `OR`, leaving a
+    // single `userId = $1 AND (title = $2 OR publishedAt IS NULL)` rather than repeating the
+    // `userId` condition inside each branch.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-or-with-shared-conjunct.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    // `userId` is selected (it's a scalar column) as well as filtered on, so it shows up once in
+    // the SELECT list regardless; a second mention is the hoisted WHERE condition. A third would
+    // mean it wasn't hoisted and still appears once per OR branch.
+    let user_id_mentions = pretty.to_lowercase().matches("userid").count();
+    assert_eq!(
+        user_id_mentions, 2,
+        "expected the shared userId conjunct to appear once (plus once in SELECT), hoisted out of the OR: {pretty}"
+    );
+    assert!(pretty.to_uppercase().contains(" OR "), "expected the remainder to stay an OR: {pretty}");
+}
+
+#[test]
+fn query_on_partitioned_model_without_shard_key_warns() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let query_schema = query_schema();
+    let mut shard_keys = HashMap::new();
+    shard_keys.insert("Post".to_owned(), "userId".to_owned());
+
+    let metrics = Rc::new(RefCell::new(query_compiler::CompileMetrics::default()));
+    let options = CompileOptions {
+        shard_keys,
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+
+    // `find-many-take-5.json` has an empty `where`, so it can't possibly constrain `userId`.
+    translate_fixture(&query_schema, include_str!("data/cases/find-many-take-5.json"), &options).unwrap();
+
+    let metrics = metrics.borrow();
+    assert!(
+        metrics.warnings.iter().any(|w| w.contains("Post") && w.contains("userId")),
+        "expected a warning about the missing shard key: {:?}",
+        metrics.warnings
+    );
+}
+
+#[test]
+fn query_on_partitioned_model_with_shard_key_does_not_warn() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let query_schema = query_schema();
+    let mut shard_keys = HashMap::new();
+    shard_keys.insert("Post".to_owned(), "userId".to_owned());
+
+    let metrics = Rc::new(RefCell::new(query_compiler::CompileMetrics::default()));
+    let options = CompileOptions {
+        shard_keys,
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+
+    translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-or-with-shared-conjunct.json"),
+        &options,
+    )
+    .unwrap();
+
+    let metrics = metrics.borrow();
+    assert!(
+        metrics.warnings.is_empty(),
+        "did not expect a shard-key warning once the filter constrains userId: {:?}",
+        metrics.warnings
+    );
+}
+
+#[test]
+fn create_returns_configured_cdc_columns_in_addition_to_the_selected_ones() {
+    // `publishedAt` is nullable and left out of the fixture's `data`, so the only way it can show
+    // up in the compiled query at all is via `cdc_columns` widening the RETURNING selection —
+    // unlike `userId`, which the insert has to write regardless of what's selected.
+    let query_schema = query_schema();
+    let mut cdc_columns = HashMap::new();
+    cdc_columns.insert("Post".to_owned(), vec!["publishedAt".to_owned()]);
+    let options = CompileOptions {
+        cdc_columns,
+        ..Default::default()
+    };
+
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/create-post-title-only-selection.json"),
+        &options,
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        pretty.contains("title") && pretty.contains("publishedAt"),
+        "expected the RETURNING to carry both the selected `title` and the CDC column `publishedAt`: {pretty}"
+    );
+}
+
+#[test]
+fn update_returns_configured_cdc_columns_in_addition_to_the_selected_ones() {
+    // A CDC subscriber cares about updates at least as much as inserts, so `cdc_columns` widens
+    // an update's `RETURNING` the same way it does a create's.
+    let query_schema = query_schema();
+    let mut cdc_columns = HashMap::new();
+    cdc_columns.insert("Post".to_owned(), vec!["publishedAt".to_owned()]);
+    let options = CompileOptions {
+        cdc_columns,
+        ..Default::default()
+    };
+
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/update-post-title-only-selection.json"),
+        &options,
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        pretty.contains("title") && pretty.contains("publishedAt"),
+        "expected the RETURNING to carry both the selected `title` and the CDC column `publishedAt`: {pretty}"
+    );
+}
+
+#[test]
+fn delete_returns_configured_cdc_columns_in_addition_to_the_selected_ones() {
+    let query_schema = query_schema();
+    let mut cdc_columns = HashMap::new();
+    cdc_columns.insert("Post".to_owned(), vec!["publishedAt".to_owned()]);
+    let options = CompileOptions {
+        cdc_columns,
+        ..Default::default()
+    };
+
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/delete-post-title-only-selection.json"),
+        &options,
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        pretty.contains("title") && pretty.contains("publishedAt"),
+        "expected the RETURNING to carry both the selected `title` and the CDC column `publishedAt`: {pretty}"
+    );
+}
+
+#[test]
+fn create_without_cdc_columns_configured_selects_only_the_requested_fields() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/create-post-title-only-selection.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        !pretty.contains("publishedAt"),
+        "did not expect publishedAt to be selected when no cdc_columns are configured: {pretty}"
+    );
+}
+
+#[test]
+fn include_total_count_adds_a_windowed_count_aliased_for_the_response_shaping_layer() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        include_total_count: true,
+        ..Default::default()
+    };
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-with-total-count.json"),
+        &options,
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("COUNT(*) OVER()"),
+        "expected a windowed COUNT(*) OVER() alongside the page of rows: {pretty}"
+    );
+    assert!(
+        pretty.contains(query_builder::TOTAL_COUNT_ALIAS),
+        "expected the windowed count to be aliased to the constant the response-shaping layer reads: {pretty}"
+    );
+}
+
+#[test]
+fn include_total_count_left_off_adds_no_windowed_count() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-with-total-count.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        !pretty.to_uppercase().contains("OVER()"),
+        "did not expect a windowed count when include_total_count is left at its default: {pretty}"
+    );
+}
+
+/// Returns the contents of the first balanced `(...)` group in `s` starting at `open_idx`, which
+/// must be the index of its opening paren, without the surrounding parens themselves.
+fn balanced_paren_contents(s: &str, open_idx: usize) -> &str {
+    assert_eq!(&s[open_idx..open_idx + 1], "(");
+
+    let mut depth = 0i32;
+    for (i, c) in s[open_idx..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &s[open_idx + 1..open_idx + i];
+                }
+            }
+            _ => {}
+        }
+    }
+
+    panic!("unbalanced parens in {:?} starting at {open_idx}", s);
+}
+
+/// Compiles the distinct+total-count fixture and returns the SQL of the derived table that the
+/// total count is computed from, with whitespace collapsed so wrapping introduced by pretty
+/// printing at a given line width can't split a token pair (e.g. `FROM` and its `(`) apart.
+fn distinct_total_count_dedup_sql(take: i64) -> String {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        include_total_count: true,
+        ..Default::default()
+    };
+    let fixture = include_str!("data/cases/find-many-distinct-with-total-count.json").replace("\"take\": 5", &format!("\"take\": {take}"));
+    let expr = translate_fixture(&query_schema, &fixture, &options).unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+    let normalized = pretty.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let marker = "COUNT(*) FROM (";
+    let marker_pos = normalized
+        .find(marker)
+        .unwrap_or_else(|| panic!("expected a `COUNT(*) FROM (...)` scalar subquery: {pretty}"));
+    balanced_paren_contents(&normalized, marker_pos + marker.len() - 1).to_owned()
+}
+
+#[test]
+fn include_total_count_with_distinct_counts_the_deduplicated_rows() {
+    // `COUNT(*) OVER()` is evaluated before `DISTINCT ON` collapses duplicate rows in standard SQL
+    // logical query processing, and `DISTINCT ON` itself only picks one row per group out of
+    // whatever its own `LIMIT`/`OFFSET` leave it to look at. So the total has to come from a
+    // `SELECT COUNT(*)` over a derived table that reran the distinct with pagination suppressed,
+    // not a windowed count layered onto the paginated `DISTINCT ON` select itself — otherwise the
+    // "total" caps out at `take` instead of reporting every distinct match.
+    //
+    // This compiler always lowers `distinct` to a native `DISTINCT ON` (see
+    // `CompileOptions::enabled_preview_features`'s doc comment), which is Postgres-only syntax, and
+    // this crate has no live database to run compiled SQL against — so the strongest check
+    // available here is structural: compile the same fixture at two different `take` values and
+    // confirm the derived table the count is based on is byte-for-byte identical between them, and
+    // contains no pagination at all. If pagination ever leaked back into that derived table, the
+    // two `take` values would stop agreeing on a total, same as the bug being fixed here.
+    let dedup_sql_take_2 = distinct_total_count_dedup_sql(2);
+    let dedup_sql_take_5 = distinct_total_count_dedup_sql(5);
+
+    assert_eq!(
+        dedup_sql_take_2, dedup_sql_take_5,
+        "the derived table a distinct query's total count is based on must not depend on `take`"
+    );
+    assert!(
+        dedup_sql_take_2.to_uppercase().contains("DISTINCT ON"),
+        "expected the derived table to still apply the distinct: {dedup_sql_take_2}"
+    );
+    for keyword in ["LIMIT", "OFFSET"] {
+        assert!(
+            !dedup_sql_take_2.to_uppercase().contains(keyword),
+            "expected no {keyword} in the derived table the total count is computed from: {dedup_sql_take_2}"
+        );
+    }
+
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        include_total_count: true,
+        ..Default::default()
+    };
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-distinct-with-total-count.json"),
+        &options,
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+    assert!(
+        pretty.contains(query_builder::TOTAL_COUNT_ALIAS),
+        "expected the count to be aliased to the constant the response-shaping layer reads: {pretty}"
+    );
+}
+
+#[test]
+fn update_with_a_version_check_matches_it_in_where_and_increments_it_in_set() {
+    // `extract_unique_filter` (query_graph_builder/extractors/filters) already ANDs the unique
+    // fields in `where` with whatever else is in there, so `where: { id, price }` reaches this
+    // compiler as a single `Filter::And([id.equals(..), price.equals(..)])` — no new WHERE-side
+    // work needed. `{ increment: .. }` already lowers to `column + value` in the SET clause (see
+    // `ScalarWriteOperation::Add` in sql-query-builder's `write.rs`) — no new SET-side work
+    // needed either. What this compiler does NOT have is a dedicated "zero rows updated" ->
+    // concurrency-conflict error: an `UpdateRecord` here just compiles to a statement wrapped in
+    // `Expression::Unique`, and mapping an empty result to a specific error (optimistic-lock
+    // conflict vs. plain not-found) happens above this crate, in the client response layer that
+    // already turns a missing `findUnique`/`update` result into `RecordNotFound`.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/update-with-version-check.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 120).unwrap();
+
+    assert!(
+        pretty.contains("Int(1)"),
+        "expected the id to appear as a WHERE equality param: {pretty}"
+    );
+    let price_mentions = pretty.to_lowercase().matches("price").count();
+    assert!(
+        price_mentions >= 2,
+        "expected `price` to appear once as a WHERE equality condition and once in the SET clause: {pretty}"
+    );
+    assert!(
+        pretty.contains('+'),
+        "expected the increment to compile to `price + value` in the SET clause: {pretty}"
+    );
+}
+
+/// Wraps a [`SqlQueryBuilder`] to report a [`QueryBuilder::max_limit`], which none of our
+/// supported connectors actually enforce, so the clamping behavior it drives can be exercised
+/// without inventing a fake cap on a real one.
+struct CappedBuilder<'a, V> {
+    inner: SqlQueryBuilder<'a, V>,
+    max_limit: i64,
+}
+
+impl<'a, V: quaint::visitor::Visitor<'a>> query_builder::QueryBuilder for CappedBuilder<'a, V> {
+    fn build_get_records(
+        &self,
+        model: &query_structure::Model,
+        query_arguments: query_structure::QueryArguments,
+        selected_fields: &query_structure::FieldSelection,
+        lock_mode: Option<LockMode>,
+        include_total_count: bool,
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner
+            .build_get_records(model, query_arguments, selected_fields, lock_mode, include_total_count)
+    }
+
+    fn build_aggregate(
+        &self,
+        model: &query_structure::Model,
+        args: query_structure::QueryArguments,
+        selections: &[query_structure::AggregationSelection],
+        group_by: Vec<query_structure::ScalarField>,
+        having: Option<query_structure::Filter>,
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_aggregate(model, args, selections, group_by, having)
+    }
+
+    fn build_create_record(
+        &self,
+        model: &query_structure::Model,
+        args: query_structure::WriteArgs,
+        selected_fields: &query_structure::FieldSelection,
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_create_record(model, args, selected_fields)
+    }
+
+    fn build_inserts(
+        &self,
+        model: &query_structure::Model,
+        args: Vec<query_structure::WriteArgs>,
+        skip_duplicates: bool,
+        selected_fields: Option<&query_structure::FieldSelection>,
+    ) -> Result<Vec<query_builder::DbQuery>, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_inserts(model, args, skip_duplicates, selected_fields)
+    }
+
+    fn build_update(
+        &self,
+        model: &query_structure::Model,
+        record_filter: query_structure::RecordFilter,
+        args: query_structure::WriteArgs,
+        selected_fields: Option<&query_structure::FieldSelection>,
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_update(model, record_filter, args, selected_fields)
+    }
+
+    fn build_updates_from_filter(
+        &self,
+        model: &query_structure::Model,
+        filter: query_structure::Filter,
+        args: query_structure::WriteArgs,
+        selected_fields: Option<&query_structure::FieldSelection>,
+        limit: Option<usize>,
+    ) -> Result<Vec<query_builder::DbQuery>, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_updates_from_filter(model, filter, args, selected_fields, limit)
+    }
+
+    fn build_upsert(
+        &self,
+        model: &query_structure::Model,
+        filter: query_structure::Filter,
+        create_args: query_structure::WriteArgs,
+        update_args: query_structure::WriteArgs,
+        selected_fields: &query_structure::FieldSelection,
+        unique_constraints: &[query_structure::ScalarField],
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner
+            .build_upsert(model, filter, create_args, update_args, selected_fields, unique_constraints)
+    }
+
+    fn build_m2m_connect(
+        &self,
+        field: query_structure::RelationField,
+        parent_id: &query_structure::SelectionResult,
+        child_ids: &[query_structure::SelectionResult],
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_m2m_connect(field, parent_id, child_ids)
+    }
+
+    fn build_m2m_disconnect(
+        &self,
+        field: query_structure::RelationField,
+        parent_id: &query_structure::SelectionResult,
+        child_ids: &[query_structure::SelectionResult],
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_m2m_disconnect(field, parent_id, child_ids)
+    }
+
+    fn build_delete(
+        &self,
+        model: &query_structure::Model,
+        filter: query_structure::RecordFilter,
+        selected_fields: Option<&query_structure::FieldSelection>,
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_delete(model, filter, selected_fields)
+    }
+
+    fn build_deletes(
+        &self,
+        model: &query_structure::Model,
+        filter: query_structure::RecordFilter,
+        limit: Option<usize>,
+    ) -> Result<Vec<query_builder::DbQuery>, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_deletes(model, filter, limit)
+    }
+
+    fn build_raw(
+        &self,
+        model: Option<&query_structure::Model>,
+        inputs: std::collections::HashMap<String, query_structure::PrismaValue>,
+        query_type: Option<String>,
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_raw(model, inputs, query_type)
+    }
+
+    fn build_savepoint(&self, name: &str) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_savepoint(name)
+    }
+
+    fn build_release_savepoint(
+        &self,
+        name: &str,
+    ) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_release_savepoint(name)
+    }
+
+    fn build_defer_constraints(&self) -> Result<query_builder::DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.build_defer_constraints()
+    }
+
+    fn max_limit(&self) -> Option<i64> {
+        Some(self.max_limit)
+    }
+}
+
+#[test]
+fn take_exceeding_the_builders_max_limit_is_clamped_with_a_warning() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = CappedBuilder {
+        inner: SqlQueryBuilder::<Postgres<'_>>::new(ctx),
+        max_limit: 2,
+    };
+
+    let op: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/find-many-take-5.json")).unwrap();
+    let graph = build_graph(&query_schema, op);
+
+    let metrics = Rc::new(RefCell::new(query_compiler::CompileMetrics::default()));
+    let options = CompileOptions {
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+    let expr = query_compiler::translate(graph, &builder, &options).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.contains("BigInt(2)"),
+        "expected the take of 5 to be clamped to the builder's max_limit of 2: {pretty}"
+    );
+    assert!(
+        !pretty.contains("BigInt(5)"),
+        "did not expect the original take to survive clamping: {pretty}"
+    );
+
+    let metrics = metrics.borrow();
+    assert!(
+        metrics.warnings.iter().any(|w| w.contains("max limit")),
+        "expected a warning about the clamp: {:?}",
+        metrics.warnings
+    );
+}
+
+#[test]
+#[traced_test]
+fn create_with_unconsumed_nested_result_is_flagged() {
+    let query_schema = query_schema();
+    translate_fixture(
+        &query_schema,
+        include_str!("data/cases/create-nested-create-unselected.json"),
+        &Default::default(),
+    )
+    .unwrap();
+
+    assert!(logs_contain(
+        "a write's result is computed but never bound to a later step or returned from the plan"
+    ));
+}
+
+#[test]
+fn translate_batch_compiles_each_operation_independently() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/create-nested-create.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-one.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let expr = query_compiler::translate_batch(graphs, &builder, &Default::default()).unwrap();
+    let Expression::Seq { statements, .. } = expr else {
+        panic!("expected a batch to translate to a Seq of its operations");
+    };
+    assert_eq!(statements.len(), 2);
+}
+
+#[test]
+fn an_independent_batch_is_pipelined_on_a_capable_builder() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/create-nested-create.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-one.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let options = CompileOptions {
+        pipelined: true,
+        ..Default::default()
+    };
+    let expr = query_compiler::translate_batch(graphs, &builder, &options).unwrap();
+    let Expression::Seq { pipelined, .. } = expr else {
+        panic!("expected a batch to translate to a Seq of its operations");
+    };
+    assert!(pipelined, "expected the independent batch to be marked pipelined");
+}
+
+#[test]
+fn a_batch_is_not_pipelined_on_a_builder_without_support() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Mysql,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Mysql<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/create-nested-create.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-one.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let options = CompileOptions {
+        pipelined: true,
+        ..Default::default()
+    };
+    let expr = query_compiler::translate_batch(graphs, &builder, &options).unwrap();
+    let Expression::Seq { pipelined, .. } = expr else {
+        panic!("expected a batch to translate to a Seq of its operations");
+    };
+    assert!(
+        !pipelined,
+        "MySQL has no multi-statement pipelining, so the hint shouldn't be set even when requested"
+    );
+}
+
+#[test]
+fn canonical_write_order_issues_independent_writes_by_table_name() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    // `User` is deleted before `Post` in the caller's own order, but `Post` sorts first
+    // alphabetically, so the database should see the `Post` delete issued first.
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/delete-one-user.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-one.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let options = CompileOptions {
+        canonical_write_order: true,
+        ..Default::default()
+    };
+    let expr = query_compiler::translate_batch(graphs, &builder, &options).unwrap();
+
+    let Expression::Let { bindings, expr } = expr else {
+        panic!("expected a reordered batch to bind each operation before returning results: {expr:?}");
+    };
+    assert_eq!(
+        bindings.iter().map(|b| b.name.as_str()).collect::<Vec<_>>(),
+        vec!["batch_write_1", "batch_write_0"],
+        "expected the Post delete (index 1) to be bound, and so issued, before the User delete (index 0)"
+    );
+
+    let Expression::Seq { statements, .. } = *expr else {
+        panic!("expected the batch's results after the bindings");
+    };
+    let names = statements
+        .iter()
+        .map(|statement| match statement {
+            Expression::Get { name } => name.as_str(),
+            other => panic!("expected a Get for each result, found {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec!["batch_write_0", "batch_write_1"],
+        "expected results to still come back in the caller's original order"
+    );
+}
+
+#[test]
+fn canonical_write_order_overrides_pipelined_with_a_warning() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/delete-one-user.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-one.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let metrics = Rc::new(RefCell::new(query_compiler::CompileMetrics::default()));
+    let options = CompileOptions {
+        canonical_write_order: true,
+        pipelined: true,
+        metrics: Some(metrics.clone()),
+        ..Default::default()
+    };
+    let expr = query_compiler::translate_batch(graphs, &builder, &options).unwrap();
+
+    // Reordering wins: the batch is still bound one write at a time, not sent as one pipelined
+    // round trip, even though `pipelined` was also requested.
+    assert!(matches!(expr, Expression::Let { .. }));
+    assert!(
+        metrics.borrow().warnings.iter().any(|w| w.contains("pipelined")),
+        "expected a warning that canonical_write_order suppressed pipelining: {:?}",
+        metrics.borrow().warnings
+    );
+}
+
+#[test]
+fn canonical_write_order_is_left_alone_when_not_requested() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/delete-one-user.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-one.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let expr = query_compiler::translate_batch(graphs, &builder, &Default::default()).unwrap();
+    let Expression::Seq { statements, .. } = expr else {
+        panic!("expected an unreordered batch to stay a flat Seq of its operations");
+    };
+    assert_eq!(statements.len(), 2);
+}
+
+#[test]
+fn translate_batch_does_not_yet_coalesce_independent_find_uniques() {
+    // `translate_batch` doesn't merge independent `findUnique`s on the same model into a
+    // single `IN` query yet (see the doc comment on `translate_batch` for why); this test
+    // pins down today's correct-but-unmerged behavior so a future coalescing pass is the one
+    // that changes it, not an accident.
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/find-unique-simple.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/find-unique-by-id-2.json")).unwrap();
+    let op3: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/find-unique-by-id-3.json")).unwrap();
+    let graphs = vec![
+        build_graph(&query_schema, op1),
+        build_graph(&query_schema, op2),
+        build_graph(&query_schema, op3),
+    ];
+
+    let expr = query_compiler::translate_batch(graphs, &builder, &Default::default()).unwrap();
+    let Expression::Seq { statements, .. } = expr else {
+        panic!("expected a batch to translate to a Seq of its operations");
+    };
+    assert_eq!(statements.len(), 3, "expected one query per findUnique, not a merged IN query");
+}
+
+#[test]
+fn traceparent_is_embedded_as_a_comment_on_every_statement() {
+    // Correlating slow-query logs with a distributed trace doesn't need a new,
+    // compiler-specific mechanism: `Context::traceparent` already threads a
+    // per-operation W3C traceparent through to every statement the builder emits,
+    // via `SqlTraceComment`. This pins down that it reaches every statement in a batch.
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let traceparent: TraceParent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap();
+    let ctx = Context::new(&connection_info, Some(traceparent));
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/find-unique-simple.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/find-unique-by-id-2.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let expr = query_compiler::translate_batch(graphs, &builder, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+
+    assert_eq!(
+        pretty.matches(&format!("traceparent='{traceparent}'")).count(),
+        2,
+        "expected the traceparent comment on both of the batch's statements: {pretty}"
+    );
+}
+
+#[test]
+fn savepoint_per_operation_wraps_each_batch_operation() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let op1: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-one.json")).unwrap();
+    let op2: JsonSingleQuery = serde_json::from_str(include_str!("data/delete-many.json")).unwrap();
+    let graphs = vec![build_graph(&query_schema, op1), build_graph(&query_schema, op2)];
+
+    let options = CompileOptions {
+        savepoint_per_operation: true,
+        ..Default::default()
+    };
+    let expr = query_compiler::translate_batch(graphs, &builder, &options).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    // Each operation contributes one `SAVEPOINT ...` and one `RELEASE SAVEPOINT ...`,
+    // and the latter contains the former as a substring, hence 4 and 2 respectively.
+    assert_eq!(pretty.matches("SAVEPOINT batch_op_").count(), 4);
+    assert_eq!(pretty.matches("RELEASE SAVEPOINT batch_op_").count(), 2);
+}
+
+#[test]
+fn columnar_option_wraps_the_plan_in_a_columnar_hint() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        columnar: true,
+        ..Default::default()
+    };
+    let expr = translate_fixture(&query_schema, include_str!("data/query-one2m.json"), &options).unwrap();
+    assert!(matches!(expr, Expression::Columnar(_)));
+}
+
+#[test]
+fn defer_constraints_option_emits_set_constraints_deferred() {
+    let query_schema = query_schema();
+    let options = CompileOptions {
+        defer_constraints: true,
+        ..Default::default()
+    };
+    let expr = translate_fixture(&query_schema, include_str!("data/cases/find-unique-simple.json"), &options).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("SET CONSTRAINTS ALL DEFERRED"),
+        "expected a deferred-constraints statement: {pretty}"
+    );
+}
+
+#[test]
+fn find_unique_does_not_require_a_transaction() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/find-unique-simple.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+
+    assert!(!expr.requires_transaction());
+}
+
+#[test]
+fn nested_create_requires_a_transaction() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/create-nested-create.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+
+    assert!(expr.requires_transaction());
+}
+
+#[test]
+fn selecting_an_unknown_field_is_rejected_before_it_reaches_the_compiler() {
+    // There's no `TranslateError::UnknownField`: a selection referencing a field that doesn't
+    // exist on the model is already rejected while parsing the request into a `QueryDocument`,
+    // long before a `QueryGraph` (let alone an `Expression`) exists for the compiler to validate.
+    let query_schema = query_schema();
+    let query: JsonSingleQuery = serde_json::from_str(include_str!("data/cases/select-unknown-field.json")).unwrap();
+    let request = RequestBody::Json(JsonBody::Single(query));
+
+    assert!(request.into_doc(&query_schema).is_err());
+}
+
+#[test]
+fn write_to_view_is_rejected() {
+    let query_schema = query_schema();
+
+    let err = translate_fixture(&query_schema, include_str!("data/views/create-on-view.json"), &Default::default())
+        .unwrap_err();
+    assert!(matches!(err, TranslateError::WriteToView(model) if model == "PostWithUserEmail"));
+
+    let find_many = include_str!("data/views/find-many-view.json");
+    assert!(translate_fixture(&query_schema, find_many, &Default::default()).is_ok());
+}
+
+#[test]
+fn empty_where_compiles_to_a_match_all_findmany() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/find-many-empty-where.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(!pretty.to_uppercase().contains("WHERE"), "expected no WHERE clause: {pretty}");
+}
+
+#[test]
+fn empty_in_filter_short_circuits_to_an_empty_result_without_a_round_trip() {
+    // `id: { in: [] }` compiles the `WHERE` clause down to quaint's constant-false `1=0`, which
+    // the optimizer recognizes and replaces with an empty result, instead of sending a query the
+    // database would answer with zero rows anyway.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-empty-in-filter.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(!pretty.to_uppercase().contains("SELECT"), "expected no query to be issued: {pretty}");
+    assert_eq!(expr.estimated_cost(), 0, "expected the short-circuited plan to cost nothing to run");
+}
+
+#[test]
+fn empty_in_filter_prunes_a_dependent_include() {
+    // The parent findMany's `id: { in: [] }` can never match, so the nested `categories` include
+    // — normally a second, separate query joined in-memory — shouldn't run either.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-empty-in-filter-with-include.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(!pretty.to_uppercase().contains("SELECT"), "expected neither query to run: {pretty}");
+    assert_eq!(expr.estimated_cost(), 0, "expected the pruned plan to cost nothing to run");
+}
+
+#[test]
+fn insensitive_starts_with_escapes_like_metacharacters_in_the_pattern() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/startswith-insensitive-escaped.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(pretty.to_uppercase().contains("ILIKE"), "expected an ILIKE filter: {pretty}");
+    // The literal `%` in the user's `startsWith: "50%"` pattern must be escaped, so it isn't
+    // mistaken for the wildcard we append ourselves to turn it into a prefix match.
+    // `pretty_print` renders bound params via `{value:?}`, which doubles up the backslash
+    // we inserted, so the escaped pattern shows up as `50\\%` in the printed text.
+    assert!(
+        pretty.contains(r"50\\%"),
+        "expected the literal % to be backslash-escaped: {pretty}"
+    );
+}
+
+#[test]
+fn default_starts_with_escapes_like_metacharacters_in_the_pattern() {
+    // Same bug as `insensitive_starts_with_escapes_like_metacharacters_in_the_pattern`, but for
+    // the plain case-sensitive `startsWith` that compiles to `LIKE` instead of `ILIKE`.
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/startswith-default-escaped.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("LIKE") && !pretty.to_uppercase().contains("ILIKE"),
+        "expected a LIKE filter: {pretty}"
+    );
+    assert!(
+        pretty.contains(r"50\\%"),
+        "expected the literal % to be backslash-escaped: {pretty}"
+    );
+}
+
+#[test]
+fn sqlite_starts_with_emits_an_explicit_like_escape_clause() {
+    // SQLite, unlike Postgres/MySQL, doesn't treat `\` as the default `LIKE` escape character, so
+    // the escaped pattern `escape_like` produces is only correct if the visitor also emits an
+    // explicit `ESCAPE '\'` clause.
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/startswith-default-escaped.json");
+    let expr = translate_fixture_sqlite(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("LIKE") && pretty.to_uppercase().contains("ESCAPE '\\'"),
+        "expected a LIKE filter with an explicit ESCAPE clause: {pretty}"
+    );
+    assert!(
+        pretty.contains(r"50\\%"),
+        "expected the literal % to be backslash-escaped: {pretty}"
+    );
+}
+
+#[test]
+fn mssql_starts_with_emits_an_explicit_like_escape_clause() {
+    // Same bug as `sqlite_starts_with_emits_an_explicit_like_escape_clause`, but for MSSQL, the
+    // other connector that doesn't default `\` to the `LIKE` escape character.
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/startswith-default-escaped.json");
+    let expr = translate_fixture_mssql(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("LIKE") && pretty.to_uppercase().contains("ESCAPE '\\'"),
+        "expected a LIKE filter with an explicit ESCAPE clause: {pretty}"
+    );
+    assert!(
+        pretty.contains(r"50\\%"),
+        "expected the literal % to be backslash-escaped: {pretty}"
+    );
+}
+
+#[test]
+fn nested_set_on_a_many_to_many_relation_disconnects_before_connecting() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/update-relation-set.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+    let upper = pretty.to_uppercase();
+
+    let delete_pos = upper.find("DELETE").expect("expected the old children to be disconnected");
+    let insert_pos = upper.find("INSERT").expect("expected the new children to be connected");
+    assert!(
+        delete_pos < insert_pos,
+        "expected old children to be disconnected before new ones are connected: {pretty}"
+    );
+}
+
+#[test]
+fn nested_create_mixed_with_connect_emits_both_an_insert_and_an_update() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/create-nested-mixed-create-connect.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+    let upper = pretty.to_uppercase();
+
+    // One INSERT for the new user, one for the nested `create`d post; one UPDATE to set the
+    // connected post's foreign key to the new user.
+    assert!(
+        upper.matches("INSERT").count() >= 2,
+        "expected an insert for the user and one for the nested `create`d post: {pretty}"
+    );
+    assert!(
+        upper.contains("UPDATE"),
+        "expected the `connect` to update the existing post's foreign key: {pretty}"
+    );
+}
+
+#[test]
+fn decimal_range_filter_compiles() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/decimal-range-filter.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(pretty.contains('>') && pretty.contains('<'), "expected a range predicate: {pretty}");
+}
+
+#[test]
+fn aggregate_count_of_a_nullable_field_is_distinct_from_count_all() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/aggregate-count-nullable-field.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+    let upper = pretty.to_uppercase();
+
+    assert!(upper.contains("COUNT(*)"), "expected `_all` to compile to COUNT(*): {pretty}");
+    assert!(
+        pretty.contains(r#"COUNT("public"."Post"."publishedAt")"#),
+        "expected `_count: {{ publishedAt: true }}` to compile to COUNT(publishedAt), not COUNT(*): {pretty}"
+    );
+}
+
+#[test]
+fn orderby_on_a_to_one_relation_compiles() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/orderby-toone-relation.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("ORDER BY"),
+        "expected an ORDER BY clause: {pretty}"
+    );
+}
+
+#[test]
+fn include_of_a_nullable_to_one_relation_is_wrapped_in_unique() {
+    // This compiler always loads a nested relation through its own query, joined in memory (see
+    // the doc comment on `add_inmemory_join`) — there's no separate "relationJoins" lowering to
+    // pick between. A to-one include, nullable or not, is wrapped in `Expression::Unique`, which
+    // collapses the related query's zero-or-one rows to `null`/the record; a dangling or absent
+    // FK is just the zero-rows case, not a distinct code path.
+    let query_schema = query_schema_with_nullable_to_one();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-include-nullable-to-one.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(pretty.contains("unique"), "expected the nested editor read to be wrapped in unique(): {pretty}");
+}
+
+#[test]
+fn disabling_relation_joins_still_forces_the_separate_query_strategy() {
+    // `enabled_preview_features` exists for a lowering that needs to pick between a feature-gated
+    // path and a stable fallback, but this compiler has no such choice for relation joins: the
+    // pick between joined and separate-query nested reads is made upstream, before a plan ever
+    // reaches this crate (see `include_of_a_nullable_to_one_relation_is_wrapped_in_unique`). So
+    // compiling the very same nested read with `relationJoins` disabled or enabled should produce
+    // the identical separate-query plan either way — pinning down that `enabled_preview_features`
+    // isn't silently changing behavior it has no lowering to change yet.
+    let query_schema = query_schema_with_nullable_to_one();
+    let fixture = include_str!("data/cases/find-many-include-nullable-to-one.json");
+
+    let without_relation_joins = translate_fixture(
+        &query_schema,
+        fixture,
+        &CompileOptions {
+            enabled_preview_features: psl::PreviewFeatures::empty(),
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .pretty_print(false, 80)
+    .unwrap();
+    assert!(
+        without_relation_joins.contains("unique"),
+        "expected the separate-query strategy's unique() wrapper: {without_relation_joins}"
+    );
+
+    let with_relation_joins = translate_fixture(
+        &query_schema,
+        fixture,
+        &CompileOptions {
+            enabled_preview_features: psl::PreviewFeature::RelationJoins.into(),
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .pretty_print(false, 80)
+    .unwrap();
+
+    assert_eq!(
+        without_relation_joins, with_relation_joins,
+        "expected relationJoins to have no effect on this compiler's output"
+    );
+}
+
+#[test]
+fn orderby_on_a_json_path_compiles() {
+    let query_schema = query_schema_with_json_field();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/orderby-json-path.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_uppercase().contains("ORDER BY"),
+        "expected an ORDER BY clause: {pretty}"
+    );
+    assert!(
+        pretty.contains("priority"),
+        "expected the order by to extract the `priority` json path: {pretty}"
+    );
+}
+
+#[test]
+fn orderby_applies_a_distinct_nulls_directive_per_field_without_cross_contamination() {
+    // MySQL has no native `NULLS FIRST`/`NULLS LAST`, so quaint emulates each field's directive
+    // with its own `IS [NOT] NULL` tiebreaker column ahead of the actual sort column. Each field
+    // computes its `Order` independently in `OrderByBuilder::build`, so a `nulls: 'first'` on one
+    // field and `nulls: 'last'` on another shouldn't leak into each other's emulation.
+    let query_schema = query_schema_with_mysql_orderby_nulls();
+    let pretty = translate_fixture_mysql(
+        &query_schema,
+        include_str!("data/cases/orderby-per-field-nulls-directive.json"),
+        &Default::default(),
+    )
+    .unwrap()
+    .pretty_print(false, 200)
+    .unwrap();
+
+    assert!(
+        pretty.contains("`nickname` IS NOT NULL") && pretty.contains("`nickname` ASC"),
+        "expected nickname's `nulls: first` to emulate as an `IS NOT NULL` tiebreaker before ASC: {pretty}"
+    );
+    assert!(
+        pretty.contains("`bio` IS NULL") && pretty.contains("`bio` DESC"),
+        "expected bio's `nulls: last` to emulate as an `IS NULL` tiebreaker before DESC, \
+         independently of nickname's directive: {pretty}"
+    );
+}
+
+#[test]
+fn to_one_include_is_wrapped_unique_while_to_many_include_stays_a_list() {
+    // `Expression` has no dedicated "empty representation" field per selection — a to-one
+    // relation's empty-vs-present shape is already encoded by wrapping its query in
+    // `Expression::Unique` (so the interpreter collapses it to a record or `null`), while a
+    // to-many relation is left as a bare list (so an empty result naturally serializes to `[]`).
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/post-include-to-one-and-to-many.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+
+    let Expression::Let { expr, .. } = expr else {
+        panic!("expected the parent binding");
+    };
+    let Expression::Let { expr, .. } = *expr else {
+        panic!("expected the linking-fields binding");
+    };
+    let Expression::Join { children, .. } = *expr else {
+        panic!("expected an in-memory join over the includes");
+    };
+
+    let user = children.iter().find(|c| c.parent_field == "user").unwrap();
+    assert!(
+        matches!(user.child, Expression::Unique(_)),
+        "expected the to-one `user` include to be wrapped in Unique: {:?}",
+        user.child
+    );
+
+    let categories = children.iter().find(|c| c.parent_field == "categories").unwrap();
+    assert!(
+        !matches!(categories.child, Expression::Unique(_)),
+        "expected the to-many `categories` include to stay a bare list: {:?}",
+        categories.child
+    );
+}
+
+#[test]
+fn where_equals_a_field_ref_compiles_to_a_column_comparison() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/where-equals-column-ref.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_lowercase().contains("userid") && pretty.to_lowercase().contains(" = "),
+        "expected a column-to-column comparison: {pretty}"
+    );
+}
+
+#[test]
+fn update_changing_primary_key_emits_cascaded_child_updates_under_relation_mode_prisma() {
+    // Under `relationMode = "prisma"`, the `QueryGraphBuilder` emulates `onUpdate: Cascade` by
+    // inserting an extra `updateMany` node for the child model into the graph before it ever
+    // reaches the compiler, so this is really a test that the compiler threads an ordinary
+    // (builder-generated) dependent write through in the right order, not new compiler logic.
+    let query_schema = query_schema_with_relation_mode_prisma();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/update-pk-cascade.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.to_lowercase().contains("cascadechild"),
+        "expected the cascaded child update to be part of the plan: {pretty}"
+    );
+    assert!(
+        pretty.to_lowercase().contains("cascadeparent"),
+        "expected the parent update to be part of the plan: {pretty}"
+    );
+}
+
+#[test]
+fn upsert_with_differing_create_and_update_fields_returns_a_uniform_shape() {
+    // `create` and `update` can touch different columns, but the compiled upsert is a single
+    // `INSERT ... ON CONFLICT DO UPDATE ... RETURNING` statement, so whichever branch fires at
+    // runtime shares one `RETURNING` clause covering every selected field. This is really a test
+    // that the builder doesn't narrow that clause to only the fields `update` happens to write.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/upsert-differing-fields.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+
+    assert!(
+        pretty.contains("ON CONFLICT") && pretty.contains("RETURNING"),
+        "expected a single upsert statement with one RETURNING clause: {pretty}"
+    );
+
+    let returning = pretty.split("RETURNING").nth(1).unwrap();
+    for column in ["\"id\"", "\"title\"", "\"userId\"", "\"publishedAt\""] {
+        assert!(
+            returning.contains(column),
+            "expected {column} in the shared RETURNING clause regardless of which branch ran: {pretty}"
+        );
+    }
+}
+
+#[test]
+fn two_level_some_none_relation_filter_builds_nested_correlated_subqueries() {
+    // `posts: { some: { categories: { none: {...} } } }` needs an outer `EXISTS` (for `some`)
+    // wrapping an inner `NOT EXISTS` (for `none`), with each subquery correlated to its own
+    // parent: the outer to `User`, the inner to the `Post` row the outer subquery is considering.
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-nested-some-none.json"),
+        &Default::default(),
+    )
+    .unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+
+    assert!(
+        pretty.contains("EXISTS") && pretty.contains("NOT EXISTS"),
+        "expected a NOT EXISTS nested inside an EXISTS: {pretty}"
+    );
+    assert!(
+        pretty.find("EXISTS").unwrap() < pretty.find("NOT EXISTS").unwrap(),
+        "expected the some's EXISTS to wrap the none's NOT EXISTS, not the other way round: {pretty}"
+    );
+    assert!(
+        pretty.to_lowercase().contains("\"public\".\"post\""),
+        "expected the outer subquery to select from Post: {pretty}"
+    );
+    assert!(
+        pretty.to_lowercase().contains("\"public\".\"category\""),
+        "expected the inner subquery to select from Category: {pretty}"
+    );
+}
+
+#[test]
+fn create_with_conflicting_scalar_fk_and_connect_is_rejected() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/create-conflicting-relation-input.json");
+    let err = translate_fixture(&query_schema, fixture, &Default::default()).unwrap_err();
+
+    assert!(matches!(
+        err,
+        TranslateError::ConflictingRelationInput { model, field }
+            if model == "Post" && field == "userId"
+    ));
+}
+
+#[test]
+fn create_with_only_a_relation_connect_compiles() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/create-relation-connect-only.json");
+    assert!(translate_fixture(&query_schema, fixture, &Default::default()).is_ok());
+}
+
+#[test]
+fn create_many_with_a_shared_timestamp_hoists_it_into_a_binding() {
+    let query_schema = query_schema();
+    let expr = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/create-many-shared-timestamp.json"),
+        &Default::default(),
+    )
+    .unwrap();
+
+    assert!(expr.bind_parameters().iter().any(|p| p.name == "@sharedTimestamp"));
+}
+
+#[test]
+fn scalar_list_has_has_every_and_has_some_use_the_postgres_array_operators() {
+    // Postgres has no dedicated "array contains element"/"array contains array" operators in
+    // SQL, so these all compile down to the `@>`/`&&` array-overlap operators: `has` and
+    // `hasEvery` both ask "is the right side a subset of the column", just with one value
+    // wrapped into a single-element array first, while `hasSome` asks "do the two arrays
+    // overlap at all".
+    let query_schema = query_schema_with_scalar_lists();
+
+    let has = translate_fixture(&query_schema, include_str!("data/cases/article-tags-has.json"), &Default::default())
+        .unwrap()
+        .pretty_print(false, 80)
+        .unwrap();
+    assert!(has.contains("@>"), "expected `has` to compile to the @> operator: {has}");
+
+    let has_every = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/article-tags-has-every.json"),
+        &Default::default(),
+    )
+    .unwrap()
+    .pretty_print(false, 80)
+    .unwrap();
+    assert!(
+        has_every.contains("@>"),
+        "expected `hasEvery` to compile to the @> operator: {has_every}"
+    );
+
+    let has_some = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/article-tags-has-some.json"),
+        &Default::default(),
+    )
+    .unwrap()
+    .pretty_print(false, 80)
+    .unwrap();
+    assert!(
+        has_some.contains("&&"),
+        "expected `hasSome` to compile to the && operator: {has_some}"
+    );
+}
+
+#[test]
+fn a_constant_repeated_across_a_batch_is_hoisted_into_one_transaction_wide_binding() {
+    let query_schema = query_schema();
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Postgres,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Postgres<'_>>::new(ctx);
+
+    let graphs = (0..5)
+        .map(|_| {
+            let op: JsonSingleQuery =
+                serde_json::from_str(include_str!("data/cases/find-user-by-tenant-email.json")).unwrap();
+            build_graph(&query_schema, op)
+        })
+        .collect();
+
+    let expr = query_compiler::translate_batch(graphs, &builder, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 80).unwrap();
+    assert_eq!(
+        pretty.matches("tenant-42").count(),
+        1,
+        "expected the literal to appear once, with every statement referencing the binding instead: {pretty}"
+    );
+
+    let Expression::Let { bindings, expr } = expr else {
+        panic!("expected the shared tenant email to be hoisted into a top-of-transaction binding: {pretty}");
+    };
+    assert_eq!(bindings.len(), 1);
+    assert!(matches!(bindings[0].expr, Expression::Value(_)));
+
+    let Expression::Seq { statements, .. } = *expr else {
+        panic!("expected the batch's statements after the binding");
+    };
+    assert_eq!(statements.len(), 5);
+}
+
+#[test]
+fn cursor_pagination_on_a_compound_primary_key_compares_and_orders_by_every_key_column() {
+    // With no explicit `orderBy`, cursor pagination falls back to ordering by the model's
+    // primary identifier; for `ParentModelWithCompositeId` that's the pair `(a, b)`, so both
+    // columns must show up in the comparison the cursor subquery builds and in the ORDER BY.
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/find-many-compound-pk-cursor.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+    let upper = pretty.to_uppercase();
+
+    let order_by_pos = upper.find("ORDER BY").expect("expected an ORDER BY clause: {pretty}");
+    let order_by_clause = &pretty[order_by_pos..];
+    assert!(
+        order_by_clause.contains(r#"."a""#) && order_by_clause.contains(r#"."b""#),
+        "expected both compound key columns in the ORDER BY: {pretty}"
+    );
+
+    let where_clause = &pretty[..order_by_pos];
+    assert!(
+        where_clause.contains(r#"."a""#) && where_clause.contains(r#"."b""#),
+        "expected both compound key columns in the cursor comparison: {pretty}"
+    );
+}
+
+#[test]
+fn self_relation_two_hop_filter_compiles_with_distinct_aliases_per_level() {
+    let query_schema = query_schema_with_self_relation();
+    let fixture = include_str!("data/cases/self-relation-two-hop-filter.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 200).unwrap();
+
+    // Each hop up the self-relation joins `Category` against itself again, so every level needs
+    // its own alias. If the two hops ever shared one, the filter would (at best) always compare a
+    // row's parent against itself.
+    assert!(
+        pretty.matches("\"Category\"").count() >= 3,
+        "expected the base table plus one aliased join per hop: {pretty}"
+    );
+    assert!(
+        pretty.to_uppercase().contains("ROOT"),
+        "expected the innermost filter's literal to survive compilation: {pretty}"
+    );
+}
+
+#[test]
+fn explicit_equals_null_compiles_to_is_null_and_omission_adds_no_filter() {
+    // `where: { editorId: { equals: null } }` and leaving `editorId` out of `where` entirely are
+    // different things: the former must still filter out rows that have an editor, the latter
+    // must not touch `editorId` at all. Only the former should have `editorId` in the SQL.
+    let query_schema = query_schema_with_nullable_to_one();
+
+    let with_explicit_null = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-explicit-equals-null.json"),
+        &Default::default(),
+    )
+    .unwrap()
+    .pretty_print(false, 120)
+    .unwrap();
+
+    assert!(
+        with_explicit_null.to_uppercase().contains("IS NULL"),
+        "expected an explicit `equals: null` to compile to `IS NULL`: {with_explicit_null}"
+    );
+
+    let without_where = translate_fixture(
+        &query_schema,
+        include_str!("data/cases/find-many-include-nullable-to-one.json"),
+        &Default::default(),
+    )
+    .unwrap()
+    .pretty_print(false, 120)
+    .unwrap();
+
+    assert!(
+        !without_where.to_uppercase().contains("IS NULL"),
+        "expected an omitted filter field to add no null check: {without_where}"
+    );
+}
+
+#[test]
+fn diff_plans_reports_a_diff_when_an_optimization_is_toggled() {
+    let query_schema = query_schema();
+    let fixtures = [(
+        "create-many-differing-fields",
+        include_str!("data/cases/create-many-differing-fields.json"),
+    )];
+
+    let unchanged = diff_plans(&query_schema, &fixtures, &Default::default(), &Default::default());
+    assert_eq!(summarize_plan_diffs(&unchanged), "1 plans unchanged, 0 changed");
+
+    let idempotent = CompileOptions {
+        idempotent: true,
+        ..Default::default()
+    };
+    let changed = diff_plans(&query_schema, &fixtures, &Default::default(), &idempotent);
+    assert_eq!(summarize_plan_diffs(&changed), "0 plans unchanged, 1 changed");
+    assert!(changed[0].is_changed());
+    assert!(!changed[0].render().is_empty());
+}
+
+#[test]
+fn lock_mode_adds_a_for_update_clause_to_a_top_level_read() {
+    let query_schema = query_schema();
+    let fixture = include_str!("data/cases/find-user-by-tenant-email.json");
+
+    let unlocked = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    assert!(!unlocked.pretty_print(false, 80).unwrap().to_uppercase().contains("FOR UPDATE"));
+
+    let options = CompileOptions {
+        lock_mode: Some(LockMode::Update),
+        ..Default::default()
+    };
+    let locked = translate_fixture(&query_schema, fixture, &options).unwrap();
+    assert!(
+        locked.pretty_print(false, 80).unwrap().to_uppercase().contains("FOR UPDATE"),
+        "expected the compiled query to lock the row it reads"
+    );
+}
+
+/// Every `Uuid(...)` literal appearing in a pretty-printed plan, in the order encountered.
+fn uuid_literals(pretty: &str) -> Vec<&str> {
+    pretty
+        .match_indices("Uuid(")
+        .map(|(i, _)| {
+            let start = i + "Uuid(".len();
+            let end = pretty[start..].find(')').map_or(pretty.len(), |e| start + e);
+            &pretty[start..end]
+        })
+        .collect()
+}
+
+#[test]
+fn nested_create_with_a_client_known_pk_threads_the_literal_instead_of_a_placeholder() {
+    let query_schema = query_schema_with_uuid_pk();
+    let fixture = include_str!("data/cases/create-nested-with-uuid-pk.json");
+    let expr = translate_fixture(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 100).unwrap();
+
+    // The account's `id` is a concrete value already in the request (its `@default(uuid())` is
+    // resolved client-side before the graph is built, same as any other literal the caller
+    // supplies). The nested device create's `accountId` would otherwise need a `var(...)` bound
+    // to the account create's query result; since the id is already known, the compiler should
+    // inline it directly and never need a placeholder here.
+    assert!(
+        !pretty.contains("var("),
+        "expected no placeholder: the account id is already known at compile time: {pretty}"
+    );
+
+    let uuids = uuid_literals(&pretty);
+    assert!(
+        uuids.len() >= 2,
+        "expected the account id literal to appear in both the account create and the device's accountId: {pretty}"
+    );
+    let mut sorted = uuids.clone();
+    sorted.sort_unstable();
+    assert!(
+        sorted.windows(2).any(|pair| pair[0] == pair[1]),
+        "expected the account id literal to be reused as-is by the nested device create: {pretty}"
+    );
+
+    // This doesn't (yet) avoid the account's own RETURNING: pruning it safely would require the
+    // query graph builder itself to know no other outgoing dependency needs it, which is out of
+    // this crate's reach (see the TODO on `WriteQuery::CreateRecord` in translate/query/write.rs).
+    assert!(pretty.to_uppercase().contains("RETURNING"));
+}
+
+/// Same as [`query_schema`], but built from a schema whose connector is MySQL, i.e. one without
+/// `InsertReturning` — so a plain `create` can't be compiled as a single `INSERT ... RETURNING`
+/// and the query graph builder adds a follow-up `find_unique` by the row's id instead (see
+/// `create_record` in `query_graph_builder::write::create`).
+fn query_schema_with_mysql() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-mysql.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`query_schema_with_mysql`], but with two nullable scalar fields to order by, for
+/// exercising MySQL's `NULLS FIRST`/`NULLS LAST` emulation (MySQL has no native syntax for it).
+fn query_schema_with_mysql_orderby_nulls() -> Arc<QuerySchema> {
+    let schema_string = include_str!("data/cases/schema-mysql-orderby-nulls.prisma");
+    let schema = psl::validate(schema_string.into());
+
+    assert!(!schema.diagnostics.has_errors(), "{:?}", schema.diagnostics);
+
+    let schema = Arc::new(schema);
+    Arc::new(query_core::schema::build(schema, true))
+}
+
+/// Same as [`translate_fixture`], but targets MySQL instead of Postgres, for the compiler paths
+/// that only show up on connectors without `InsertReturning`.
+fn translate_fixture_mysql(
+    query_schema: &Arc<QuerySchema>,
+    json: &str,
+    options: &CompileOptions,
+) -> TranslateResult<Expression> {
+    let connection_info = ConnectionInfo::External(ExternalConnectionInfo::new(
+        SqlFamily::Mysql,
+        "public".to_owned(),
+        None,
+    ));
+    let ctx = Context::new(&connection_info, None);
+    let builder = SqlQueryBuilder::<Mysql<'_>>::new(ctx);
+
+    let query: JsonSingleQuery = serde_json::from_str(json).unwrap();
+    let graph = build_graph(query_schema, query);
+
+    query_compiler::translate(graph, &builder, options)
+}
+
+#[test]
+fn create_on_a_connector_without_returning_reuses_the_follow_up_reads_result() {
+    let query_schema = query_schema_with_mysql();
+    let fixture = include_str!("data/cases/create-account-mysql.json");
+    let expr = translate_fixture_mysql(&query_schema, fixture, &Default::default()).unwrap();
+    let pretty = expr.pretty_print(false, 100).unwrap();
+
+    // A non-atomic create still compiles a `find_unique` node in the graph alongside the create,
+    // but since it asks for nothing the create's own result doesn't already have, it should never
+    // turn into a second SQL query.
+    assert_eq!(
+        pretty.matches("query(").count(),
+        1,
+        "expected only the create's own query, with the follow-up read collapsed into it: {pretty}"
+    );
+    assert!(
+        pretty.contains("get("),
+        "expected the follow-up read's binding to just reuse the create's result: {pretty}"
+    );
+}
@@ -286,6 +286,26 @@ impl QueryGraph {
         }
     }
 
+    /// Returns `root` together with every node reachable from it by following
+    /// outgoing edges.
+    pub fn subgraph_nodes(&self, root: &NodeRef) -> Vec<NodeRef> {
+        let mut nodes = vec![*root];
+        let mut i = 0;
+
+        while i < nodes.len() {
+            let node = nodes[i];
+            i += 1;
+
+            for (_, child) in self.child_pairs(&node) {
+                if !nodes.contains(&child) {
+                    nodes.push(child);
+                }
+            }
+        }
+
+        nodes
+    }
+
     /// Returns all root nodes of the graph.
     /// A root node is defined by having no incoming edges.
     pub fn root_nodes(&self) -> Vec<NodeRef> {
@@ -338,6 +358,13 @@ impl QueryGraph {
         self.needs_transaction
     }
 
+    /// Looks up a node by the identifier previously returned from [`NodeRef::id`].
+    pub fn node_by_id(&self, id: &str) -> Option<NodeRef> {
+        let index: usize = id.parse().ok()?;
+        let node_ix = self.graph.from_index(index);
+        self.graph.node_weight(node_ix).map(|_| NodeRef { node_ix })
+    }
+
     /// Returns a reference to the content of `node`, if the content is still present.
     pub fn node_content(&self, node: &NodeRef) -> Option<&Node> {
         self.graph.node_weight(node.node_ix).unwrap().borrow()
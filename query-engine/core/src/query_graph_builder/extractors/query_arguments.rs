@@ -3,7 +3,7 @@ use crate::{
     query_document::{ParsedArgument, ParsedInputMap},
     QueryGraphBuilderError, QueryGraphBuilderResult,
 };
-use query_structure::{prelude::*, QueryArguments};
+use query_structure::{prelude::*, JsonFilterPath, QueryArguments};
 use schema::constants::{aggregations, args, ordering};
 use std::convert::TryInto;
 
@@ -134,6 +134,12 @@ fn process_order_object(
                     process_order_object(&rf.related_model().into(), object, path, None)
                 }
 
+                Field::Scalar(sf)
+                    if sf.type_identifier() == TypeIdentifier::Json && is_json_path_ordering(&field_value) =>
+                {
+                    Ok(Some(extract_order_by_json(sf, field_value)?))
+                }
+
                 Field::Scalar(sf) => {
                     let (sort_order, nulls_order) = extract_order_by_args(field_value)?;
 
@@ -202,6 +208,49 @@ fn extract_order_by_relevance(
     Ok(Some(OrderBy::relevance(fields, search, sort_order, path)))
 }
 
+fn is_json_path_ordering(field_value: &ParsedInputValue<'_>) -> bool {
+    matches!(field_value, ParsedInputValue::Map(map) if map.contains_key(ordering::PATH))
+}
+
+fn extract_order_by_json(sf: ScalarFieldRef, field_value: ParsedInputValue<'_>) -> QueryGraphBuilderResult<OrderBy> {
+    let mut map: ParsedInputMap<'_> = field_value.try_into()?;
+
+    let sort: PrismaValue = map.swap_remove(ordering::SORT).unwrap().try_into()?;
+    let sort_order = pv_to_sort_order(sort)?;
+
+    let json_path = extract_json_path(map.swap_remove(ordering::PATH).unwrap())?;
+
+    let numeric = match map.swap_remove(ordering::TYPE) {
+        Some(value) => {
+            let pv: PrismaValue = value.try_into()?;
+            pv.into_string().unwrap() == ordering::NUMBER
+        }
+        None => false,
+    };
+
+    Ok(OrderBy::json(sf, json_path, sort_order, numeric))
+}
+
+fn extract_json_path(value: ParsedInputValue<'_>) -> QueryGraphBuilderResult<JsonFilterPath> {
+    let path: PrismaValue = value.try_into()?;
+
+    match path {
+        PrismaValue::String(str) => Ok(JsonFilterPath::String(str)),
+        PrismaValue::List(list) => {
+            let keys = list
+                .into_iter()
+                .map(|key| {
+                    key.into_string()
+                        .expect("Json ordering array path elements must all be of type string")
+                })
+                .collect();
+
+            Ok(JsonFilterPath::Array(keys))
+        }
+        _ => unreachable!(),
+    }
+}
+
 fn extract_sort_aggregation(field_name: &str) -> Option<SortAggregation> {
     match field_name {
         aggregations::UNDERSCORE_COUNT => Some(SortAggregation::Count),
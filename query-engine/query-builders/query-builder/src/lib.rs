@@ -1,8 +1,8 @@
 use std::{collections::HashMap, fmt};
 
 use query_structure::{
-    AggregationSelection, FieldSelection, Filter, Model, PrismaValue, QueryArguments, RecordFilter, RelationField,
-    ScalarCondition, ScalarField, SelectionResult, WriteArgs,
+    AggregationSelection, FieldSelection, Filter, Model, PlaceholderType, PrismaValue, QueryArguments, RecordFilter,
+    RelationField, ScalarCondition, ScalarField, SelectionResult, WriteArgs,
 };
 use serde::Serialize;
 mod query_arguments_ext;
@@ -15,6 +15,8 @@ pub trait QueryBuilder {
         model: &Model,
         query_arguments: QueryArguments,
         selected_fields: &FieldSelection,
+        lock_mode: Option<LockMode>,
+        include_total_count: bool,
     ) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>>;
 
     /// Retrieve related records through an M2M relation.
@@ -111,8 +113,71 @@ pub trait QueryBuilder {
         inputs: HashMap<String, PrismaValue>,
         query_type: Option<String>,
     ) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Marks a point within the current transaction that a later
+    /// [`build_release_savepoint`](QueryBuilder::build_release_savepoint) or
+    /// rollback can refer to by `name`.
+    fn build_savepoint(&self, name: &str) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Forgets the savepoint `name` created by
+    /// [`build_savepoint`](QueryBuilder::build_savepoint), once the work
+    /// after it has completed and no longer needs to be rolled back to.
+    fn build_release_savepoint(&self, name: &str) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Defers checking of the current transaction's deferrable constraints
+    /// (e.g. foreign keys) until commit time, instead of after each
+    /// statement. On connectors without deferrable constraints this is a
+    /// no-op statement rather than an error, since it's always safe to skip.
+    fn build_defer_constraints(&self) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Whether the connector this builder targets can send several statements in one round trip
+    /// (e.g. Postgres's extended query protocol pipelining), letting a compiled plan's independent
+    /// statements be batched instead of waited on one at a time. Defaults to `false`, the safe
+    /// choice for connectors that don't support it.
+    fn supports_pipelining(&self) -> bool {
+        false
+    }
+
+    /// The SQL cast to append after a placeholder bound to `r#type`, e.g. `"int4"` so `$1` becomes
+    /// `$1::int4`, for `query-compiler`'s `CompileOptions::explicit_casts`. Some driver adapters
+    /// can't infer a parameter's type from context the way a native driver can, and fail with
+    /// "could not determine data type of parameter" unless every placeholder carries one
+    /// explicitly. Returns `None` when this connector doesn't need the hint, or doesn't have a
+    /// cast to offer for `r#type` — the placeholder is then left bare.
+    fn placeholder_cast(&self, _r#type: &PlaceholderType) -> Option<String> {
+        None
+    }
+
+    /// The largest value this connector accepts in a single query's `LIMIT`, if it enforces one
+    /// (e.g. some connectors reject or silently truncate a `LIMIT` above a fixed cap). A `take`
+    /// beyond this is clamped by `query-compiler` rather than sent through and rejected by the
+    /// database. Returns `None` when this connector has no such cap.
+    fn max_limit(&self) -> Option<i64> {
+        None
+    }
 }
 
+/// A row-level lock requested on the rows a read query returns, e.g. via
+/// `SELECT ... FOR UPDATE`. It's the caller's responsibility to only request
+/// one inside a transaction; outside of one, the lock is released as soon as
+/// the statement finishes and has no useful effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Blocks other transactions from locking, updating, or deleting the
+    /// selected rows until the current transaction ends.
+    Update,
+    /// Blocks other transactions from updating or deleting the selected
+    /// rows, while still letting them take their own share lock.
+    Share,
+}
+
+/// Column alias `build_get_records` gives the total-count column it adds to each row when called
+/// with `include_total_count: true`: a windowed `COUNT(*) OVER()`, or a scalar count subquery over
+/// the unpaginated rows when the query has `distinct`. A response-shaping layer pulls the total out of
+/// this column on the first row (it's the same on every row) and strips it back out of the
+/// per-record result before handing the page to the client.
+pub const TOTAL_COUNT_ALIAS: &str = "_totalCount";
+
 #[derive(Debug)]
 pub struct RelationLink {
     field: RelationField,
@@ -143,10 +208,25 @@ impl fmt::Display for RelationLink {
 pub struct DbQuery {
     pub query: String,
     pub params: Vec<PrismaValue>,
+    /// Set by the query builder when it already proved, while constructing the query's filter,
+    /// that its predicate can never match a row (e.g. an empty `id: { in: [] }` collapsing to a
+    /// statically-false condition). A caller can use this to skip issuing the query, without
+    /// having to re-derive the same fact from the rendered SQL text, which is only ever a proxy
+    /// for what the builder already knew structurally.
+    pub known_empty: bool,
 }
 
 impl DbQuery {
     pub fn new(query: String, params: Vec<PrismaValue>) -> Self {
-        Self { query, params }
+        Self {
+            query,
+            params,
+            known_empty: false,
+        }
+    }
+
+    pub fn with_known_empty(mut self, known_empty: bool) -> Self {
+        self.known_empty = known_empty;
+        self
     }
 }
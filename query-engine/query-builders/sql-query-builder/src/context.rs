@@ -1,6 +1,6 @@
 use std::sync::{self, atomic::AtomicUsize};
 
-use quaint::prelude::ConnectionInfo;
+use quaint::prelude::{ConnectionInfo, SqlFamily};
 use telemetry::TraceParent;
 
 use crate::filter::alias::Alias;
@@ -41,6 +41,10 @@ impl<'a> Context<'a> {
         self.connection_info.schema_name()
     }
 
+    pub(crate) fn sql_family(&self) -> SqlFamily {
+        self.connection_info.sql_family()
+    }
+
     pub fn max_insert_rows(&self) -> Option<usize> {
         self.max_insert_rows
     }
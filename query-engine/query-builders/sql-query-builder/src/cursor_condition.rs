@@ -444,6 +444,7 @@ fn order_definitions(
             OrderBy::ScalarAggregation(order_by) => cursor_order_def_aggregation_scalar(order_by, order_by_def),
             OrderBy::ToManyAggregation(order_by) => cursor_order_def_aggregation_rel(order_by, order_by_def),
             OrderBy::Relevance(order_by) => cursor_order_def_relevance(order_by, order_by_def),
+            OrderBy::Json(order_by) => cursor_order_def_json(order_by, order_by_def),
         })
         .collect_vec()
 }
@@ -517,6 +518,17 @@ fn cursor_order_def_relevance(order_by: &OrderByRelevance, order_by_def: &OrderB
     }
 }
 
+/// Build a CursorOrderDefinition for an order by on a value extracted from a `Json` field. Like
+/// relevance ordering, there's no relation path and so no foreign keys to check for nullability.
+fn cursor_order_def_json(order_by: &OrderByJson, order_by_def: &OrderByDefinition) -> CursorOrderDefinition {
+    CursorOrderDefinition {
+        sort_order: order_by.sort_order,
+        order_column: order_by_def.order_column.clone(),
+        order_fks: None,
+        on_nullable_fields: false,
+    }
+}
+
 fn foreign_keys_from_order_path(path: &[OrderByHop], joins: &[AliasedJoin]) -> Option<Vec<CursorOrderForeignKey>> {
     let (before_last_hop, last_hop) = take_last_two_elem(path);
 
@@ -831,13 +831,20 @@ pub(crate) fn default_scalar_filter(
     alias: Option<Alias>,
     ctx: &Context<'_>,
 ) -> ConditionTree<'static> {
+    // NOTE: a placeholder's value isn't known until execution, so a bound `equals` can turn out
+    // to be `null` at runtime, and plain `=` is SQL's three-valued `UNKNOWN` rather than the
+    // `IS NULL` match a literal `equals: null` gets below. We don't special-case that here: this
+    // same `Equals(Placeholder)` shape is also how a relation join's key condition is compiled
+    // (see `translate_read_query` in query-compiler), where strict `=` is the *correct* behavior —
+    // a child row whose foreign key is `null` must not match a join on a `null` parent key. The two
+    // uses aren't distinguishable at this layer, so rewriting one would silently break the other.
     let condition = match cond {
         ScalarCondition::Equals(ConditionValue::Value(PrismaValue::Null)) => comparable.is_null(),
         ScalarCondition::NotEquals(ConditionValue::Value(PrismaValue::Null)) => comparable.is_not_null(),
         ScalarCondition::Equals(value) => comparable.equals(convert_first_value(fields, value, alias, ctx)),
         ScalarCondition::NotEquals(value) => comparable.not_equals(convert_first_value(fields, value, alias, ctx)),
         ScalarCondition::Contains(value) => match value {
-            ConditionValue::Value(value) => comparable.like(format!("%{value}%")),
+            ConditionValue::Value(value) => comparable.like(format!("%{}%", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => comparable.like(quaint::ast::concat::<'_, Expression<'_>>(vec![
                 Value::text("%").raw().into(),
                 field_ref.aliased_col(alias, ctx).into(),
@@ -845,7 +852,7 @@ pub(crate) fn default_scalar_filter(
             ])),
         },
         ScalarCondition::NotContains(value) => match value {
-            ConditionValue::Value(value) => comparable.not_like(format!("%{value}%")),
+            ConditionValue::Value(value) => comparable.not_like(format!("%{}%", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => {
                 comparable.not_like(quaint::ast::concat::<'_, Expression<'_>>(vec![
                     Value::text("%").raw().into(),
@@ -855,14 +862,14 @@ pub(crate) fn default_scalar_filter(
             }
         },
         ScalarCondition::StartsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.like(format!("{value}%")),
+            ConditionValue::Value(value) => comparable.like(format!("{}%", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => comparable.like(quaint::ast::concat::<'_, Expression<'_>>(vec![
                 field_ref.aliased_col(alias, ctx).into(),
                 Value::text("%").raw().into(),
             ])),
         },
         ScalarCondition::NotStartsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.not_like(format!("{value}%")),
+            ConditionValue::Value(value) => comparable.not_like(format!("{}%", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => {
                 comparable.not_like(quaint::ast::concat::<'_, Expression<'_>>(vec![
                     field_ref.aliased_col(alias, ctx).into(),
@@ -871,14 +878,14 @@ pub(crate) fn default_scalar_filter(
             }
         },
         ScalarCondition::EndsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.like(format!("%{value}")),
+            ConditionValue::Value(value) => comparable.like(format!("%{}", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => comparable.like(quaint::ast::concat::<'_, Expression<'_>>(vec![
                 Value::text("%").raw().into(),
                 field_ref.aliased_col(alias, ctx).into(),
             ])),
         },
         ScalarCondition::NotEndsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.not_like(format!("%{value}")),
+            ConditionValue::Value(value) => comparable.not_like(format!("%{}", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => {
                 comparable.not_like(quaint::ast::concat::<'_, Expression<'_>>(vec![
                     Value::text("%").raw().into(),
@@ -971,6 +978,18 @@ pub(crate) fn default_scalar_filter(
     ConditionTree::single(condition)
 }
 
+/// Escapes the characters `LIKE`/`ILIKE` treat specially (`\`, `%`, `_`) in a user-supplied
+/// `contains`/`startsWith`/`endsWith` value — scalar or JSON string/array — so that e.g. a
+/// literal `%` in the search term isn't interpreted as a wildcard once we wrap it in our own
+/// `%...%` pattern. Every connector's `visit_like`/`visit_not_like` emits an explicit
+/// `ESCAPE '\'` clause (`quaint/src/visitor.rs`), so `\` is the escape character everywhere, not
+/// just on the connectors where it happens to be the implicit default — every `LIKE` pattern
+/// built from a user value has to go through here, or that `\` gets misread as the start of an
+/// escape sequence instead of a literal character.
+fn escape_like(value: PrismaValue) -> String {
+    format!("{value}").replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
 fn insensitive_scalar_filter(
     comparable: Expression<'static>,
     cond: ScalarCondition,
@@ -995,7 +1014,7 @@ fn insensitive_scalar_filter(
             }
         },
         ScalarCondition::Contains(value) => match value {
-            ConditionValue::Value(value) => comparable.compare_raw("ILIKE", format!("%{value}%")),
+            ConditionValue::Value(value) => comparable.compare_raw("ILIKE", format!("%{}%", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => comparable.compare_raw(
                 "ILIKE",
                 concat::<'_, Expression<'_>>(vec![
@@ -1006,7 +1025,9 @@ fn insensitive_scalar_filter(
             ),
         },
         ScalarCondition::NotContains(value) => match value {
-            ConditionValue::Value(value) => comparable.compare_raw("NOT ILIKE", format!("%{value}%")),
+            ConditionValue::Value(value) => {
+                comparable.compare_raw("NOT ILIKE", format!("%{}%", escape_like(value)))
+            }
             ConditionValue::FieldRef(field_ref) => comparable.compare_raw(
                 "NOT ILIKE",
                 concat::<'_, Expression<'_>>(vec![
@@ -1017,28 +1038,32 @@ fn insensitive_scalar_filter(
             ),
         },
         ScalarCondition::StartsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.compare_raw("ILIKE", format!("{value}%")),
+            ConditionValue::Value(value) => comparable.compare_raw("ILIKE", format!("{}%", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => comparable.compare_raw(
                 "ILIKE",
                 concat::<'_, Expression<'_>>(vec![field_ref.aliased_col(alias, ctx).into(), Value::text("%").into()]),
             ),
         },
         ScalarCondition::NotStartsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.compare_raw("NOT ILIKE", format!("{value}%")),
+            ConditionValue::Value(value) => {
+                comparable.compare_raw("NOT ILIKE", format!("{}%", escape_like(value)))
+            }
             ConditionValue::FieldRef(field_ref) => comparable.compare_raw(
                 "NOT ILIKE",
                 concat::<'_, Expression<'_>>(vec![field_ref.aliased_col(alias, ctx).into(), Value::text("%").into()]),
             ),
         },
         ScalarCondition::EndsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.compare_raw("ILIKE", format!("%{value}")),
+            ConditionValue::Value(value) => comparable.compare_raw("ILIKE", format!("%{}", escape_like(value))),
             ConditionValue::FieldRef(field_ref) => comparable.compare_raw(
                 "ILIKE",
                 concat::<'_, Expression<'_>>(vec![Value::text("%").into(), field_ref.aliased_col(alias, ctx).into()]),
             ),
         },
         ScalarCondition::NotEndsWith(value) => match value {
-            ConditionValue::Value(value) => comparable.compare_raw("NOT ILIKE", format!("%{value}")),
+            ConditionValue::Value(value) => {
+                comparable.compare_raw("NOT ILIKE", format!("%{}", escape_like(value)))
+            }
             ConditionValue::FieldRef(field_ref) => comparable.compare_raw(
                 "NOT ILIKE",
                 concat::<'_, Expression<'_>>(vec![Value::text("%").into(), field_ref.aliased_col(alias, ctx).into()]),
@@ -1281,8 +1306,10 @@ impl JsonFilterExt for (Expression<'static>, Expression<'static>) {
             // string_contains (value)
             (ConditionValue::Value(value), JsonTargetType::String) => {
                 let contains = match query_mode {
-                    QueryMode::Default => expr_string.like(format!("%{value}%")),
-                    QueryMode::Insensitive => Expression::from(lower(expr_string)).like(lower(format!("%{value}%"))),
+                    QueryMode::Default => expr_string.like(format!("%{}%", escape_like(value))),
+                    QueryMode::Insensitive => {
+                        Expression::from(lower(expr_string)).like(lower(format!("%{}%", escape_like(value))))
+                    }
                 };
 
                 if reverse {
@@ -1357,8 +1384,10 @@ impl JsonFilterExt for (Expression<'static>, Expression<'static>) {
             // string_starts_with (value)
             (ConditionValue::Value(value), JsonTargetType::String) => {
                 let starts_with = match query_mode {
-                    QueryMode::Default => expr_string.like(format!("{value}%")),
-                    QueryMode::Insensitive => Expression::from(lower(expr_string)).like(lower(format!("{value}%"))),
+                    QueryMode::Default => expr_string.like(format!("{}%", escape_like(value))),
+                    QueryMode::Insensitive => {
+                        Expression::from(lower(expr_string)).like(lower(format!("{}%", escape_like(value))))
+                    }
                 };
 
                 if reverse {
@@ -1428,8 +1457,10 @@ impl JsonFilterExt for (Expression<'static>, Expression<'static>) {
             // string_ends_with (value)
             (ConditionValue::Value(value), JsonTargetType::String) => {
                 let ends_with = match query_mode {
-                    QueryMode::Default => expr_string.like(format!("%{value}")),
-                    QueryMode::Insensitive => Expression::from(lower(expr_string)).like(lower(format!("%{value}"))),
+                    QueryMode::Default => expr_string.like(format!("%{}", escape_like(value))),
+                    QueryMode::Insensitive => {
+                        Expression::from(lower(expr_string)).like(lower(format!("%{}", escape_like(value))))
+                    }
                 };
 
                 if reverse {
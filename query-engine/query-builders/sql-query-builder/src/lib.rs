@@ -21,10 +21,10 @@ use quaint::{
     ast::{Column, Comparable, ConditionTree, Query, Row, Values},
     visitor::Visitor,
 };
-use query_builder::{DbQuery, QueryBuilder};
+use query_builder::{DbQuery, LockMode, QueryBuilder};
 use query_structure::{
-    AggregationSelection, FieldSelection, Filter, Model, ModelProjection, QueryArguments, RecordFilter, RelationField,
-    ScalarField, SelectionResult, WriteArgs,
+    AggregationSelection, FieldSelection, Filter, Model, ModelProjection, PlaceholderType, QueryArguments,
+    RecordFilter, RelationField, ScalarField, SelectionResult, WriteArgs,
 };
 
 pub use column_metadata::ColumnMetadata;
@@ -53,12 +53,15 @@ impl<'a, V> SqlQueryBuilder<'a, V> {
     where
         V: Visitor<'a>,
     {
+        let query = query.into();
+        let known_empty = matches!(&query, Query::Select(select) if select.is_known_empty());
+
         let (sql, params) = V::build(query)?;
         let params = params
             .into_iter()
             .map(convert::quaint_value_to_prisma_value)
             .collect::<Vec<_>>();
-        Ok(DbQuery::new(sql, params))
+        Ok(DbQuery::new(sql, params).with_known_empty(known_empty))
     }
 }
 
@@ -68,6 +71,8 @@ impl<'a, V: Visitor<'a>> QueryBuilder for SqlQueryBuilder<'a, V> {
         model: &Model,
         query_arguments: QueryArguments,
         selected_fields: &FieldSelection,
+        lock_mode: Option<LockMode>,
+        include_total_count: bool,
     ) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>> {
         let query = read::get_records(
             model,
@@ -76,8 +81,20 @@ impl<'a, V: Visitor<'a>> QueryBuilder for SqlQueryBuilder<'a, V> {
                 .mark_all_selected(),
             selected_fields.virtuals(),
             query_arguments,
+            include_total_count,
             &self.context,
         );
+        // MSSQL and SQLite have no equivalent of `FOR UPDATE`/`FOR SHARE`; silently
+        // skip the lock rather than emitting SQL they'd reject.
+        let query = match (lock_mode, self.context.sql_family()) {
+            (Some(lock_mode), quaint::prelude::SqlFamily::Postgres | quaint::prelude::SqlFamily::Mysql) => {
+                query.lock(match lock_mode {
+                    LockMode::Update => quaint::ast::Lock::Update,
+                    LockMode::Share => quaint::ast::Lock::Share,
+                })
+            }
+            _ => query,
+        };
         self.convert_query(query)
     }
 
@@ -309,6 +326,76 @@ impl<'a, V: Visitor<'a>> QueryBuilder for SqlQueryBuilder<'a, V> {
         let params = inputs.remove("parameters").unwrap().into_list().unwrap();
         Ok(DbQuery::new(query, params))
     }
+
+    fn build_savepoint(&self, name: &str) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        let sql = match self.context.sql_family() {
+            // T-SQL has no `SAVEPOINT` keyword; a named savepoint is created with `SAVE TRANSACTION`.
+            quaint::prelude::SqlFamily::Mssql => format!("SAVE TRANSACTION {name}"),
+            _ => format!("SAVEPOINT {name}"),
+        };
+        Ok(DbQuery::new(sql, Vec::new()))
+    }
+
+    fn build_release_savepoint(&self, name: &str) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        let sql = match self.context.sql_family() {
+            // T-SQL savepoints are released implicitly when the transaction commits or the next
+            // `SAVE TRANSACTION` with the same name is issued; there's no explicit release statement.
+            quaint::prelude::SqlFamily::Mssql => format!("-- no-op: savepoint {name} is released implicitly on MSSQL"),
+            _ => format!("RELEASE SAVEPOINT {name}"),
+        };
+        Ok(DbQuery::new(sql, Vec::new()))
+    }
+
+    fn build_defer_constraints(&self) -> Result<DbQuery, Box<dyn std::error::Error + Send + Sync>> {
+        let sql = match self.context.sql_family() {
+            // Only Postgres supports deferrable constraints; the others check them eagerly no
+            // matter what, so there's nothing to defer.
+            quaint::prelude::SqlFamily::Postgres => "SET CONSTRAINTS ALL DEFERRED".to_owned(),
+            _ => "-- no-op: this connector has no deferrable constraints".to_owned(),
+        };
+        Ok(DbQuery::new(sql, Vec::new()))
+    }
+
+    fn supports_pipelining(&self) -> bool {
+        // Postgres's extended query protocol can pipeline several statements into one round
+        // trip; the other connectors we support don't have an equivalent.
+        self.context.sql_family() == quaint::prelude::SqlFamily::Postgres
+    }
+
+    fn placeholder_cast(&self, r#type: &PlaceholderType) -> Option<String> {
+        // `$1::int4`-style casts are Postgres syntax; the other connectors either don't need the
+        // hint (their drivers resolve parameter types fine on their own) or don't use positional
+        // `$N` placeholders to begin with.
+        if self.context.sql_family() != quaint::prelude::SqlFamily::Postgres {
+            return None;
+        }
+
+        postgres_cast_name(r#type)
+    }
+}
+
+/// The Postgres type name to cast a placeholder to, if one exists. `String` is cast to `text`
+/// even though it's also what a Prisma enum resolves to (see
+/// [`TypeIdentifier::to_placeholder_type`](query_structure::TypeIdentifier::to_placeholder_type)) —
+/// a `text` cast on a value actually destined for an enum column is wrong, but we have no way at
+/// this layer to tell the two apart, so this deliberately under-casts rather than guesses a
+/// concrete enum type name. `Object` (JSON) and `Any` (unknown/unsupported) are left uncast:
+/// Postgres infers `json`/`jsonb` well enough from context, and there's no sound default for
+/// `Any`.
+fn postgres_cast_name(r#type: &PlaceholderType) -> Option<String> {
+    let name = match r#type {
+        PlaceholderType::Any | PlaceholderType::Object => return None,
+        PlaceholderType::String => "text",
+        PlaceholderType::Int => "int4",
+        PlaceholderType::BigInt => "int8",
+        PlaceholderType::Float => "float8",
+        PlaceholderType::Boolean => "bool",
+        PlaceholderType::Decimal => "numeric",
+        PlaceholderType::Date => "timestamptz",
+        PlaceholderType::Bytes => "bytea",
+        PlaceholderType::Array(inner) => return postgres_cast_name(inner).map(|inner| format!("{inner}[]")),
+    };
+    Some(name.to_owned())
 }
 
 pub fn chunked_conditions<F, Q>(
@@ -53,6 +53,10 @@ impl OrderByBuilder {
                     reachable_only_with_capability!(ConnectorCapability::NativeFullTextSearch);
                     self.build_order_relevance(order_by, needs_reversed_order, ctx)
                 }
+                OrderBy::Json(order_by) => {
+                    reachable_only_with_capability!(ConnectorCapability::JsonOrdering);
+                    self.build_order_json(order_by, needs_reversed_order, ctx)
+                }
             })
             .collect_vec()
     }
@@ -95,6 +99,31 @@ impl OrderByBuilder {
         }
     }
 
+    /// Builds an `ORDER BY` on the value extracted from `order_by.field` at `order_by.json_path`,
+    /// e.g. `ORDER BY ("data"#>ARRAY['priority'])::jsonb` on Postgres. Doesn't support a relation
+    /// path like [`Self::build_order_scalar`] does yet, so there are never any joins to compute.
+    fn build_order_json(
+        &mut self,
+        order_by: &OrderByJson,
+        needs_reversed_order: bool,
+        ctx: &Context<'_>,
+    ) -> OrderByDefinition {
+        let order: Option<Order> = Some(into_order(&order_by.sort_order, None, needs_reversed_order));
+        let column = order_by.field.as_column(ctx).opt_table(self.parent_alias.clone());
+        let path = match &order_by.json_path {
+            JsonFilterPath::String(path) => JsonPath::string(path.clone()),
+            JsonFilterPath::Array(path) => JsonPath::array(path.clone()),
+        };
+        let order_column: Expression = json_extract(column, path, !order_by.numeric).into();
+        let order_definition: OrderDefinition = (order_column.clone(), order);
+
+        OrderByDefinition {
+            order_column,
+            order_definition,
+            joins: vec![],
+        }
+    }
+
     fn build_order_aggr_scalar(
         &mut self,
         order_by: &OrderByScalarAggregation,
@@ -22,6 +22,21 @@ pub trait SelectDefinition {
         virtual_selections: impl IntoIterator<Item = &'a VirtualSelection>,
         ctx: &Context<'_>,
     ) -> (Select<'static>, Vec<Expression<'static>>);
+
+    /// A `Select` whose row count is what `include_total_count` should report, or `None` if
+    /// `into_select`'s own output is already a safe base for that (true unless this definition
+    /// bakes both `DISTINCT`/`DISTINCT ON` and pagination into the same `Select`, since a windowed
+    /// `COUNT(*) OVER()` added there would then count at most `take` deduplicated rows instead of
+    /// every one of them). Takes `&self` rather than consuming it, since `into_select` does and
+    /// `get_records` needs both.
+    fn total_count_select<'a>(
+        &self,
+        _model: &Model,
+        _virtual_selections: impl IntoIterator<Item = &'a VirtualSelection>,
+        _ctx: &Context<'_>,
+    ) -> Option<Select<'static>> {
+        None
+    }
 }
 
 impl SelectDefinition for Filter {
@@ -59,6 +74,26 @@ impl SelectDefinition for Select<'static> {
 }
 
 impl SelectDefinition for QueryArguments {
+    fn total_count_select<'a>(
+        &self,
+        model: &Model,
+        virtual_selections: impl IntoIterator<Item = &'a VirtualSelection>,
+        ctx: &Context<'_>,
+    ) -> Option<Select<'static>> {
+        self.distinct.as_ref()?;
+
+        // Re-derive the same query but with pagination suppressed, via the flags that already
+        // exist to let the core ask for that (e.g. when it applies `take`/`skip` in memory
+        // instead). This gives a `Select` with the same joins/conditions/ordering/`DISTINCT ON`,
+        // so counting its rows reports every distinct match, not just the current page.
+        let mut unpaginated = self.clone();
+        unpaginated.ignore_take = true;
+        unpaginated.ignore_skip = true;
+
+        let (select, _) = unpaginated.into_select(model, virtual_selections, ctx);
+        Some(select)
+    }
+
     fn into_select<'a>(
         self,
         model: &Model,
@@ -135,19 +170,48 @@ pub fn get_records<'a, T>(
     columns: impl Iterator<Item = Column<'static>>,
     virtual_selections: impl IntoIterator<Item = &'a VirtualSelection>,
     query: T,
+    include_total_count: bool,
     ctx: &Context<'_>,
 ) -> Select<'static>
 where
     T: SelectDefinition,
 {
-    let (select, additional_selection_set) = query.into_select(model, virtual_selections, ctx);
+    let virtual_selections: Vec<&'a VirtualSelection> = virtual_selections.into_iter().collect();
+
+    let total_count_select = include_total_count
+        .then(|| query.total_count_select(model, virtual_selections.iter().copied(), ctx))
+        .flatten();
+
+    let (select, additional_selection_set) = query.into_select(model, virtual_selections.iter().copied(), ctx);
     let select = columns.fold(select, |acc, col| acc.column(col));
 
     let select = select.add_traceparent(ctx.traceparent);
 
-    additional_selection_set
+    let select = additional_selection_set
         .into_iter()
-        .fold(select, |acc, col| acc.value(col))
+        .fold(select, |acc, col| acc.value(col));
+
+    if !include_total_count {
+        return select;
+    }
+
+    match total_count_select {
+        Some(dedup_select) => {
+            // Count the deduplicated rows via a scalar subquery, independent of `select`'s own
+            // pagination, rather than a windowed `COUNT(*) OVER()` alongside `select`: the latter
+            // would be evaluated over at most `take` rows once LIMIT/OFFSET are baked into the
+            // same `Select` that `DISTINCT ON` needs for correct per-group row selection.
+            let count_subquery =
+                Select::from_table(Table::from(dedup_select).alias("distinct_sub")).value(count(asterisk()));
+            let total_count = Expression::from(count_subquery).alias(query_builder::TOTAL_COUNT_ALIAS);
+            select.value(total_count)
+        }
+        None => {
+            let total_count = Function::from(windowed_count(asterisk()).over(Over::default()))
+                .alias(query_builder::TOTAL_COUNT_ALIAS);
+            select.value(total_count)
+        }
+    }
 }
 
 /// Generates a query of the form:
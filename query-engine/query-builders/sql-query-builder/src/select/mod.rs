@@ -595,6 +595,7 @@ fn order_by_selection(rs: &RelationSelection) -> FieldSelection {
             // This is necessary because the order by is done on a different join. The following hops are handled by the order by builder.
             OrderBy::ToManyAggregation(x) => first_hop_linking_fields(x.intermediary_hops()),
             OrderBy::ScalarAggregation(x) => vec![x.field.clone()],
+            OrderBy::Json(x) => vec![x.field.clone()],
         })
         .collect();
 
@@ -58,6 +58,11 @@ impl Model {
             .filter(|idx| idx.is_unique())
             .filter(|index| !index.fields().any(|f| f.is_unsupported()))
     }
+
+    /// Whether this model maps to a database view rather than a table.
+    pub fn is_view(&self) -> bool {
+        self.walker().ast_model().is_view()
+    }
 }
 
 impl std::fmt::Debug for Model {
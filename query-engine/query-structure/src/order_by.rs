@@ -1,4 +1,4 @@
-use crate::{CompositeFieldRef, RelationFieldRef, ScalarFieldRef};
+use crate::{CompositeFieldRef, JsonFilterPath, RelationFieldRef, ScalarFieldRef};
 use std::fmt::Display;
 
 #[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
@@ -28,6 +28,7 @@ pub enum OrderBy {
     ScalarAggregation(OrderByScalarAggregation),
     ToManyAggregation(OrderByToManyAggregation),
     Relevance(OrderByRelevance),
+    Json(OrderByJson),
 }
 
 impl OrderBy {
@@ -37,6 +38,7 @@ impl OrderBy {
             OrderBy::ToManyAggregation(o) => Some(&o.path),
             OrderBy::ScalarAggregation(_) => None,
             OrderBy::Relevance(_) => None,
+            OrderBy::Json(_) => None,
         }
     }
 
@@ -46,6 +48,7 @@ impl OrderBy {
             OrderBy::ScalarAggregation(o) => o.sort_order,
             OrderBy::ToManyAggregation(o) => o.sort_order,
             OrderBy::Relevance(o) => o.sort_order,
+            OrderBy::Json(o) => o.sort_order,
         }
     }
 
@@ -55,6 +58,7 @@ impl OrderBy {
             OrderBy::ScalarAggregation(o) => Some(o.field.clone()),
             OrderBy::ToManyAggregation(_) => None,
             OrderBy::Relevance(_) => None,
+            OrderBy::Json(o) => Some(o.field.clone()),
         }
     }
 
@@ -112,6 +116,15 @@ impl OrderBy {
             path,
         })
     }
+
+    pub fn json(field: ScalarFieldRef, json_path: JsonFilterPath, sort_order: SortOrder, numeric: bool) -> Self {
+        Self::Json(OrderByJson {
+            field,
+            json_path,
+            sort_order,
+            numeric,
+        })
+    }
 }
 
 /// Describes a hop over to a relation or composite for an orderBy statement.
@@ -210,6 +223,20 @@ pub struct OrderByRelevance {
     pub path: Vec<OrderByHop>,
 }
 
+/// Orders by a value extracted from a `Json` field at `json_path`, e.g. `orderBy: { meta: { path:
+/// ["priority"], sort: "asc" } }`. Doesn't support relation or composite hops yet: unlike
+/// [`OrderByScalar`], the path only ever reaches into the JSON document itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OrderByJson {
+    pub field: ScalarFieldRef,
+    pub json_path: JsonFilterPath,
+    pub sort_order: SortOrder,
+    /// Extracts the value as a number instead of text, so e.g. `10` sorts after `9` instead of
+    /// before it (lexicographically, `"10"` < `"9"`). Only meaningful for paths that are known to
+    /// always point at a JSON number; the caller is responsible for picking the right one.
+    pub numeric: bool,
+}
+
 impl Display for SortOrder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -71,6 +71,13 @@ pub(crate) fn order_by_relevance_enum(container: ParentContainer, values: Vec<St
     EnumType::string(ident, values)
 }
 
+pub(crate) fn json_order_by_value_type_enum() -> EnumType {
+    EnumType::string(
+        Identifier::new_prisma(ordering::JSON_ORDER_BY_VALUE_TYPE),
+        vec![ordering::STRING.to_owned(), ordering::NUMBER.to_owned()],
+    )
+}
+
 pub(crate) fn query_mode_enum() -> EnumType {
     let ident = Identifier::new_prisma("QueryMode");
     EnumType::string(
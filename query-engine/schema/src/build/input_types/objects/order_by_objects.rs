@@ -135,6 +135,10 @@ fn orderby_field_mapper<'a>(
                 types.push(InputType::object(sort_nulls_object_type()));
             }
 
+            if ctx.has_capability(ConnectorCapability::JsonOrdering) && sf.type_identifier() == TypeIdentifier::Json {
+                types.push(InputType::object(json_order_by_object_type(ctx)));
+            }
+
             Some(input_field(sf.name().to_owned(), types, None).optional())
         }
 
@@ -177,6 +181,28 @@ fn sort_nulls_object_type<'a>() -> InputObjectType<'a> {
     input_object
 }
 
+fn json_order_by_object_type<'a>(ctx: &'_ QuerySchema) -> InputObjectType<'a> {
+    let ident = Identifier::new_prisma("JsonOrderByInput");
+
+    let path_type = if ctx.has_capability(ConnectorCapability::JsonFilteringJsonPath) {
+        InputType::string()
+    } else if ctx.has_capability(ConnectorCapability::JsonFilteringArrayPath) {
+        InputType::list(InputType::string())
+    } else {
+        unreachable!()
+    };
+
+    let mut input_object = init_input_object_type(ident);
+    input_object.set_fields(move || {
+        vec![
+            simple_input_field(ordering::SORT, InputType::Enum(sort_order_enum()), None),
+            simple_input_field(ordering::PATH, path_type, None),
+            simple_input_field(ordering::TYPE, InputType::Enum(json_order_by_value_type_enum()), None).optional(),
+        ]
+    });
+    input_object
+}
+
 fn order_by_field_aggregate<'a>(
     name: impl Into<Cow<'a, str>>,
     suffix: &str,
@@ -140,6 +140,13 @@ pub mod ordering {
     pub const SORT: &str = "sort";
     pub const NULLS: &str = "nulls";
     pub const FIELDS: &str = "fields";
+
+    // Json path ordering specifics
+    pub const PATH: &str = "path";
+    pub const TYPE: &str = "type";
+    pub const JSON_ORDER_BY_VALUE_TYPE: &str = "JsonOrderByValueType";
+    pub const NUMBER: &str = "number";
+    pub const STRING: &str = "string";
 }
 
 pub mod json_null {